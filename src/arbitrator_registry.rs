@@ -0,0 +1,650 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LookupMap, UnorderedMap};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::serde::Serialize;
+use near_sdk::{env, ext_contract, near_bindgen, AccountId, Balance, Promise};
+
+
+// The arbitrator registry keeps the arbitrators approved by the foundation to settle disputes on
+// Tenderbox tenders. A tender with a dispute requests an arbitrator here, the arbitrator rules on
+// the tender, which executes the ruling and closes the case to release the arbitrator fee.
+
+#[macro_use]
+mod errors;
+use crate::errors::*;
+
+mod foundation;
+use crate::foundation::*;
+
+mod timelock;
+use crate::timelock::*;
+
+mod types;
+
+mod interfaces;
+use crate::interfaces::*;
+
+mod events;
+use crate::events::*;
+
+mod storage;
+use crate::storage::*;
+
+mod utils;
+use crate::utils::*;
+
+
+// The stake an arbitrator has to lock to be assigned cases, until the foundation changes it.
+const DEFAULT_ARBITRATOR_STAKE: Balance = 50_000_000_000_000_000_000_000_000;
+
+pub mod gas {
+    use near_sdk::Gas;
+
+    pub use crate::interfaces::gas::*;
+
+    /// The amount of Gas the contract will attach to the callback to itself.
+    /// The base for the execution and the base for walking the arbitrators.
+    pub const CALLBACK: Gas = BASE * 2;
+}
+
+/// Stored registry record of an arbitrator.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Arbitrator {
+    /// Whether the arbitrator is approved by the foundation. Only approved arbitrators are assigned cases.
+    pub approved: bool,
+    /// The stake locked by the arbitrator.
+    pub stake: Balance,
+    /// The number of cases assigned to the arbitrator that are not closed yet.
+    pub open_cases: u64,
+    /// The number of cases the arbitrator closed.
+    pub closed_cases: u64,
+}
+
+/// Arbitrator entry returned by the views.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ArbitratorView {
+    /// The account ID of the arbitrator.
+    pub account_id: AccountId,
+    /// Whether the arbitrator is approved by the foundation.
+    pub approved: bool,
+    /// The stake locked by the arbitrator.
+    pub stake: U128,
+    /// The number of cases assigned to the arbitrator that are not closed yet.
+    pub open_cases: u64,
+    /// The number of cases the arbitrator closed.
+    pub closed_cases: u64,
+    /// Whether the arbitrator can be assigned new cases.
+    pub eligible: bool,
+}
+
+/// Dispute of a tender assigned to an arbitrator.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Case {
+    /// The account ID of the assigned arbitrator.
+    pub arbitrator_id: AccountId,
+    /// The arbitrator fee paid by the tender from the dispute deposits, released when the case is closed.
+    pub fee: U128,
+    /// The block timestamp in nanoseconds the arbitrator was assigned at.
+    pub assigned_at: U64,
+}
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ArbitratorRegistry {
+    /// The Tenderbox foundation role. It approves the arbitrators.
+    pub foundation: FoundationRole,
+
+    /// The sequence of the events emitted by the contract.
+    pub events: EventLog,
+
+    /// The account ID of the verify tender contract. Arbitrators are only assigned to tenders verified there.
+    pub verify_tender_account_id: AccountId,
+
+    /// The arbitrators by account ID, including resigned or removed ones until they withdraw their stake.
+    pub arbitrators: UnorderedMap<AccountId, Arbitrator>,
+
+    /// The open cases by tender account ID and dispute ID.
+    pub cases: LookupMap<(AccountId, u64), Case>,
+
+    /// The index in `arbitrators` the round-robin assignment continues from.
+    pub next_arbitrator_index: u64,
+
+    /// The stake an arbitrator has to lock to be assigned cases.
+    pub arbitrator_stake: Timelocked<Balance>,
+
+    /// The delay configuration changes wait before taking effect, in nanoseconds.
+    pub timelock_delay: Timelocked<u64>,
+}
+
+/// External interface for the callbacks to self.
+#[ext_contract(ext_self)]
+pub trait ExtSelf {
+    fn on_arbitrator_requested(
+        &mut self,
+        tender_account_id: AccountId,
+        dispute_id: u64,
+        agreed_arbitrator_id: Option<AccountId>,
+        fee: U128,
+    ) -> Option<AccountId>;
+}
+
+impl Default for ArbitratorRegistry {
+    fn default() -> Self {
+        Error::NotInitialized.panic()
+    }
+}
+
+#[near_bindgen]
+impl ArbitratorRegistry {
+    /// Initializes the registry with the given Tenderbox foundation account ID and the account ID
+    /// of the verify tender contract.
+    #[init]
+    pub fn new(foundation_account_id: AccountId, verify_tender_account_id: AccountId) -> Self {
+        require!(!env::state_exists(), Error::AlreadyInitialized);
+        require!(
+            env::is_valid_account_id(foundation_account_id.as_bytes()),
+            Error::InvalidFoundationAccountId
+        );
+        require!(
+            env::is_valid_account_id(verify_tender_account_id.as_bytes()),
+            Error::InvalidVerifyTenderAccountId
+        );
+        Self {
+            foundation: FoundationRole::new(foundation_account_id),
+            events: EventLog::new(),
+            verify_tender_account_id,
+            arbitrators: UnorderedMap::new(StorageKey::Arbitrators),
+            cases: LookupMap::new(StorageKey::ArbitrationCases),
+            next_arbitrator_index: 0,
+            arbitrator_stake: Timelocked::new(DEFAULT_ARBITRATOR_STAKE),
+            timelock_delay: Timelocked::new(DEFAULT_TIMELOCK_DELAY),
+        }
+    }
+
+    /// Returns the account ID of the Tenderbox foundation.
+    pub fn get_foundation_account_id(&self) -> AccountId {
+        self.foundation.account_id().clone()
+    }
+
+    /// Returns the sequence number of the last event emitted by the contract, `0` if none was emitted yet.
+    pub fn get_last_event_seq(&self) -> u64 {
+        self.events.last_seq()
+    }
+
+    /// Returns the stake an arbitrator has to lock to be assigned cases.
+    pub fn get_arbitrator_stake(&self) -> U128 {
+        self.arbitrator_stake.get().into()
+    }
+
+    /// Returns the announced change of the arbitrator stake that is not in effect yet, if any.
+    pub fn get_pending_arbitrator_stake(&self) -> Option<PendingChange<U128>> {
+        self.arbitrator_stake.pending().map(|change| PendingChange {
+            value: change.value.into(),
+            effective_at: change.effective_at,
+        })
+    }
+
+    /// Returns the given arbitrator, if it is in the registry.
+    pub fn get_arbitrator(&self, arbitrator_id: AccountId) -> Option<ArbitratorView> {
+        self.arbitrators
+            .get(&arbitrator_id)
+            .map(|arbitrator| self.internal_view(arbitrator_id, arbitrator))
+    }
+
+    /// Returns up to `limit` arbitrators of the registry, starting at `from_index`.
+    pub fn get_arbitrators(&self, from_index: u64, limit: u64) -> Vec<ArbitratorView> {
+        let keys = self.arbitrators.keys_as_vector();
+        let values = self.arbitrators.values_as_vector();
+        (from_index..std::cmp::min(from_index + limit, keys.len()))
+            .map(|index| self.internal_view(keys.get(index).unwrap(), values.get(index).unwrap()))
+            .collect()
+    }
+
+    /// Returns `true` if the given account ID is an approved arbitrator whose stake covers the current requirement.
+    pub fn is_eligible(&self, arbitrator_id: AccountId) -> bool {
+        self.arbitrators
+            .get(&arbitrator_id)
+            .map(|arbitrator| self.internal_is_eligible(&arbitrator))
+            .unwrap_or(false)
+    }
+
+    /// Returns the open case of the given dispute of the given tender, if any.
+    pub fn get_case(&self, tender_account_id: AccountId, dispute_id: u64) -> Option<Case> {
+        self.cases.get(&(tender_account_id, dispute_id))
+    }
+
+    /**************/
+    /* Arbitrator */
+    /**************/
+
+    /// Adds the attached deposit to the stake of the predecessor.
+    /// Returns the new stake.
+    /// This method can only be called by an approved arbitrator.
+    #[payable]
+    pub fn stake(&mut self) -> U128 {
+        require!(env::attached_deposit() > 0, Error::DepositNotPositive);
+        let arbitrator_id = env::predecessor_account_id();
+        let mut arbitrator = self
+            .arbitrators
+            .get(&arbitrator_id)
+            .filter(|arbitrator| arbitrator.approved)
+            .unwrap_or_else(|| Error::NotArbitrator.panic());
+        arbitrator.stake += env::attached_deposit();
+        self.arbitrators.insert(&arbitrator_id, &arbitrator);
+        arbitrator.stake.into()
+    }
+
+    /// Stops the assignment of new cases to the predecessor. The cases already assigned stay with it.
+    /// Returns `true` if the arbitrator was approved before, `false` otherwise.
+    pub fn resign(&mut self) -> bool {
+        self.internal_revoke_approval(&env::predecessor_account_id())
+    }
+
+    /// Removes the predecessor from the registry and sends its stake back.
+    /// The arbitrator has to resign or be removed by the foundation and close its open cases first.
+    pub fn withdraw_stake(&mut self) -> Promise {
+        let arbitrator_id = env::predecessor_account_id();
+        let arbitrator = self
+            .arbitrators
+            .get(&arbitrator_id)
+            .unwrap_or_else(|| Error::ArbitratorNotFound.panic());
+        require!(!arbitrator.approved, Error::ArbitratorStillApproved);
+        require!(arbitrator.open_cases == 0, Error::ArbitratorHasOpenCases);
+        self.arbitrators.remove(&arbitrator_id);
+        Promise::new(arbitrator_id).transfer(arbitrator.stake)
+    }
+
+    /**********/
+    /* Tender */
+    /**********/
+
+    /// Requests an arbitrator for the given dispute of the calling tender, attaching the arbitrator fee
+    /// paid from the dispute deposits. The arbitrator both parties agreed on is assigned if it is eligible,
+    /// otherwise the next eligible arbitrator in round-robin order.
+    /// The request is only handled once the verify tender contract confirms the predecessor is a verified tender.
+    #[payable]
+    pub fn request_arbitrator(
+        &mut self,
+        dispute_id: u64,
+        agreed_arbitrator_id: Option<AccountId>,
+    ) -> Promise {
+        let tender_account_id = env::predecessor_account_id();
+        require!(
+            self.cases.get(&(tender_account_id.clone(), dispute_id)).is_none(),
+            Error::CaseAlreadyOpen
+        );
+        ext_verify_tender::is_verified(
+            tender_account_id.clone(),
+            &self.verify_tender_account_id,
+            NO_DEPOSIT,
+            gas::verify_tender::IS_VERIFIED,
+        )
+        .then(ext_self::on_arbitrator_requested(
+            tender_account_id,
+            dispute_id,
+            agreed_arbitrator_id,
+            env::attached_deposit().into(),
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            gas::CALLBACK,
+        ))
+    }
+
+    /// Callback after the verify tender contract was asked about the requesting tender.
+    /// Opens the case and returns the assigned arbitrator if the tender is verified and an arbitrator is
+    /// eligible, otherwise refunds the fee to the tender and returns `None`.
+    pub fn on_arbitrator_requested(
+        &mut self,
+        tender_account_id: AccountId,
+        dispute_id: u64,
+        agreed_arbitrator_id: Option<AccountId>,
+        fee: U128,
+    ) -> Option<AccountId> {
+        assert_self();
+        let tender_verified = promise_result_as_bool();
+        let case_key = (tender_account_id.clone(), dispute_id);
+        let arbitrator_id = if !tender_verified || self.cases.get(&case_key).is_some() {
+            None
+        } else {
+            match agreed_arbitrator_id {
+                Some(arbitrator_id) if self.is_eligible(arbitrator_id.clone()) => Some(arbitrator_id),
+                _ => self.internal_next_arbitrator(),
+            }
+        };
+
+        let arbitrator_id = match arbitrator_id {
+            Some(arbitrator_id) => arbitrator_id,
+            None => {
+                self.events.emit(
+                    format!(
+                        "No arbitrator was assigned to the dispute #{} of @{}. Returning the fee of {}",
+                        dispute_id, tender_account_id, fee.0
+                    )
+                    .as_bytes(),
+                );
+                if fee.0 > 0 {
+                    Promise::new(tender_account_id).transfer(fee.0);
+                }
+                return None;
+            }
+        };
+
+        let mut arbitrator = self.arbitrators.get(&arbitrator_id).unwrap();
+        arbitrator.open_cases += 1;
+        self.arbitrators.insert(&arbitrator_id, &arbitrator);
+        self.cases.insert(
+            &case_key,
+            &Case {
+                arbitrator_id: arbitrator_id.clone(),
+                fee,
+                assigned_at: env::block_timestamp().into(),
+            },
+        );
+        self.events.emit(
+            format!(
+                "The arbitrator @{} was assigned to the dispute #{} of @{}",
+                arbitrator_id, dispute_id, tender_account_id
+            )
+            .as_bytes(),
+        );
+        Some(arbitrator_id)
+    }
+
+    /// Closes the case of the given dispute of the calling tender once it executed the ruling,
+    /// and pays the fee to the arbitrator.
+    pub fn close_case(&mut self, dispute_id: u64) -> Promise {
+        let tender_account_id = env::predecessor_account_id();
+        let case = self
+            .cases
+            .remove(&(tender_account_id.clone(), dispute_id))
+            .unwrap_or_else(|| Error::CaseNotFound.panic());
+        let mut arbitrator = self.arbitrators.get(&case.arbitrator_id).unwrap();
+        arbitrator.open_cases -= 1;
+        arbitrator.closed_cases += 1;
+        self.arbitrators.insert(&case.arbitrator_id, &arbitrator);
+        self.events.emit(
+            format!(
+                "The dispute #{} of @{} was closed by the arbitrator @{}",
+                dispute_id, tender_account_id, case.arbitrator_id
+            )
+            .as_bytes(),
+        );
+        Promise::new(case.arbitrator_id).transfer(case.fee.0)
+    }
+
+    /**************/
+    /* Tenderbox Foundation */
+    /**************/
+
+    /// Approves the given account ID as an arbitrator. It is assigned cases once its stake covers the requirement.
+    /// Returns `true` if the arbitrator was not approved before, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn approve_arbitrator(&mut self, arbitrator_id: AccountId) -> bool {
+        self.assert_called_by_foundation();
+        require!(env::is_valid_account_id(arbitrator_id.as_bytes()), Error::InvalidAccountId);
+        let mut arbitrator = self.arbitrators.get(&arbitrator_id).unwrap_or(Arbitrator {
+            approved: false,
+            stake: 0,
+            open_cases: 0,
+            closed_cases: 0,
+        });
+        if arbitrator.approved {
+            return false;
+        }
+        arbitrator.approved = true;
+        self.arbitrators.insert(&arbitrator_id, &arbitrator);
+        self.events.emit(format!("The arbitrator @{} was approved", arbitrator_id).as_bytes());
+        true
+    }
+
+    /// Stops the assignment of new cases to the given arbitrator. The cases already assigned stay with it.
+    /// Returns `true` if the arbitrator was approved before, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn remove_arbitrator(&mut self, arbitrator_id: AccountId) -> bool {
+        self.assert_called_by_foundation();
+        self.internal_revoke_approval(&arbitrator_id)
+    }
+
+    /// Announces a new stake arbitrators have to lock, in effect after the timelock delay.
+    /// Arbitrators below the new amount are not assigned cases until they top up.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn set_arbitrator_stake(&mut self, arbitrator_stake: U128) {
+        self.assert_called_by_foundation();
+        let effective_at = self
+            .arbitrator_stake
+            .schedule(arbitrator_stake.0, self.timelock_delay.get());
+        self.events.emit(
+            format!(
+                "The arbitrator stake changes to {} at {}",
+                arbitrator_stake.0, effective_at
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Announces a new timelock delay in nanoseconds, in effect after the current timelock delay.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn set_timelock_delay(&mut self, timelock_delay: U64) {
+        self.assert_called_by_foundation();
+        let effective_at = self
+            .timelock_delay
+            .schedule(timelock_delay.0, self.timelock_delay.get());
+        self.events.emit(
+            format!(
+                "The timelock delay changes to {} at {}",
+                timelock_delay.0, effective_at
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Cancels the configuration changes that are not in effect yet.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn cancel_pending_config(&mut self) {
+        self.assert_called_by_foundation();
+        self.arbitrator_stake.cancel();
+        self.timelock_delay.cancel();
+        self.events.emit(b"The pending configuration changes are cancelled");
+    }
+
+    /// Proposes the given account ID, e.g. a Sputnik DAO, as the new Tenderbox foundation account.
+    /// The role is only handed over once the proposed account calls `accept_foundation_role`,
+    /// which a DAO does through a function-call proposal.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn propose_foundation_account_id(&mut self, foundation_account_id: AccountId) {
+        self.assert_called_by_foundation();
+        self.foundation.propose(foundation_account_id);
+    }
+
+    /// Accepts the Tenderbox foundation role proposed to the predecessor.
+    /// This method can only be called by the account proposed with `propose_foundation_account_id`.
+    pub fn accept_foundation_role(&mut self) {
+        self.foundation.accept();
+        self.events.emit(
+            format!(
+                "The Tenderbox foundation role was handed over to @{}",
+                self.foundation.account_id()
+            )
+            .as_bytes(),
+        );
+    }
+
+    /************/
+    /* Internal */
+    /************/
+
+    /// Internal method to verify the predecessor was the Tenderbox Foundation account ID.
+    fn assert_called_by_foundation(&self) {
+        self.foundation.assert_called_by();
+    }
+
+    /// Internal method returning `true` if the arbitrator can be assigned new cases.
+    fn internal_is_eligible(&self, arbitrator: &Arbitrator) -> bool {
+        arbitrator.approved && arbitrator.stake >= self.arbitrator_stake.get()
+    }
+
+    /// Internal method returning the next eligible arbitrator in round-robin order, if any.
+    fn internal_next_arbitrator(&mut self) -> Option<AccountId> {
+        let keys = self.arbitrators.keys_as_vector();
+        let values = self.arbitrators.values_as_vector();
+        for offset in 0..keys.len() {
+            let index = (self.next_arbitrator_index + offset) % keys.len();
+            if self.internal_is_eligible(&values.get(index).unwrap()) {
+                self.next_arbitrator_index = index + 1;
+                return keys.get(index);
+            }
+        }
+        None
+    }
+
+    /// Internal method revoking the approval of the given arbitrator.
+    fn internal_revoke_approval(&mut self, arbitrator_id: &AccountId) -> bool {
+        match self.arbitrators.get(arbitrator_id) {
+            Some(mut arbitrator) if arbitrator.approved => {
+                arbitrator.approved = false;
+                self.arbitrators.insert(arbitrator_id, &arbitrator);
+                self.events.emit(
+                    format!("The arbitrator @{} is no longer approved", arbitrator_id).as_bytes(),
+                );
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Internal method building the view of the given arbitrator.
+    fn internal_view(&self, account_id: AccountId, arbitrator: Arbitrator) -> ArbitratorView {
+        ArbitratorView {
+            eligible: self.internal_is_eligible(&arbitrator),
+            account_id,
+            approved: arbitrator.approved,
+            stake: arbitrator.stake.into(),
+            open_cases: arbitrator.open_cases,
+            closed_cases: arbitrator.closed_cases,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::{testing_env, MockedBlockchain, PromiseResult};
+
+    mod test_utils;
+    use test_utils::*;
+
+    #[test]
+    fn test_arbitrator_assignment() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_arbitrator_registry())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = ArbitratorRegistry::new(account_tenderbox(), account_verified());
+        assert!(contract.approve_arbitrator(account_arbitrator(0)));
+        assert!(contract.approve_arbitrator(account_arbitrator(1)));
+        assert!(!contract.approve_arbitrator(account_arbitrator(1)));
+
+        // Only arbitrators with enough stake are eligible
+        for index in 0..2 {
+            context.predecessor_account_id = account_arbitrator(index);
+            context.attached_deposit = DEFAULT_ARBITRATOR_STAKE;
+            testing_env!(context.clone());
+            contract.stake();
+        }
+        context.attached_deposit = 0;
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.is_eligible(account_arbitrator(0)));
+        assert!(!contract.is_eligible(account_arbitrator(2)));
+
+        // Two disputes are assigned in round-robin order, a third to the arbitrator the parties agreed on
+        context.is_view = false;
+        context.predecessor_account_id = account_tender();
+        context.attached_deposit = ntoy(1);
+        testing_env!(context.clone());
+        contract.request_arbitrator(0, None);
+
+        context.predecessor_account_id = account_arbitrator_registry();
+        testing_env_with_promise_results(
+            context.clone(),
+            PromiseResult::Successful(b"true".to_vec()),
+        );
+        assert_eq!(
+            contract.on_arbitrator_requested(account_tender(), 0, None, ntoy(1).into()),
+            Some(account_arbitrator(0))
+        );
+        assert_eq!(
+            contract.on_arbitrator_requested(account_tender(), 1, None, ntoy(1).into()),
+            Some(account_arbitrator(1))
+        );
+        assert_eq!(
+            contract.on_arbitrator_requested(
+                account_tender(),
+                2,
+                Some(account_arbitrator(1)),
+                ntoy(1).into()
+            ),
+            Some(account_arbitrator(1))
+        );
+
+        // An account that isn't a verified tender gets no arbitrator
+        testing_env_with_promise_results(
+            context.clone(),
+            PromiseResult::Successful(b"false".to_vec()),
+        );
+        assert_eq!(
+            contract.on_arbitrator_requested(account_tender_owner(), 0, None, ntoy(1).into()),
+            None
+        );
+
+        // The tender closes a case once it executed the ruling
+        context.predecessor_account_id = account_tender();
+        context.attached_deposit = 0;
+        testing_env!(context.clone());
+        contract.close_case(1);
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.get_case(account_tender(), 1).is_none());
+        assert_eq!(contract.get_case(account_tender(), 2).unwrap().arbitrator_id, account_arbitrator(1));
+        let arbitrator = contract.get_arbitrator(account_arbitrator(1)).unwrap();
+        assert_eq!(arbitrator.open_cases, 1);
+        assert_eq!(arbitrator.closed_cases, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "E534: The arbitrator still has open cases")]
+    fn test_withdraw_stake_with_open_cases() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_arbitrator_registry())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = ArbitratorRegistry::new(account_tenderbox(), account_verified());
+        contract.approve_arbitrator(account_arbitrator(0));
+
+        context.predecessor_account_id = account_arbitrator(0);
+        context.attached_deposit = DEFAULT_ARBITRATOR_STAKE;
+        testing_env!(context.clone());
+        contract.stake();
+
+        context.predecessor_account_id = account_arbitrator_registry();
+        context.attached_deposit = 0;
+        testing_env_with_promise_results(
+            context.clone(),
+            PromiseResult::Successful(b"true".to_vec()),
+        );
+        contract.on_arbitrator_requested(account_tender(), 0, None, ntoy(1).into());
+
+        // Resigning keeps the assigned case, so the stake stays locked until it is closed
+        context.predecessor_account_id = account_arbitrator(0);
+        testing_env!(context.clone());
+        assert!(contract.resign());
+        contract.withdraw_stake();
+    }
+}
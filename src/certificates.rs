@@ -0,0 +1,540 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LookupMap, UnorderedMap, UnorderedSet};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, ext_contract, near_bindgen, AccountId, Promise};
+
+
+// The certificates contract mints a completion certificate to the supplier and to the issuer of a
+// verified tender once it is completed. It implements the views of NEP-171, but the certificates
+// are non-transferable: they prove a track record and can't be sold.
+
+#[macro_use]
+mod errors;
+use crate::errors::*;
+
+mod foundation;
+use crate::foundation::*;
+
+mod types;
+
+mod interfaces;
+use crate::interfaces::*;
+
+mod events;
+use crate::events::*;
+
+mod storage;
+use crate::storage::*;
+
+mod utils;
+use crate::utils::*;
+
+
+pub mod gas {
+    use near_sdk::Gas;
+
+    pub use crate::interfaces::gas::*;
+
+    /// The amount of Gas the contract will attach to the callback to itself.
+    /// The base for the execution and the base for minting the certificates.
+    pub const CALLBACK: Gas = BASE * 2;
+}
+
+pub type TokenId = String;
+
+/// Metadata of the certificates contract, as defined by NEP-177.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NFTContractMetadata {
+    pub spec: String,
+    pub name: String,
+    pub symbol: String,
+}
+
+/// Metadata of a certificate, a subset of the token metadata defined by NEP-177.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenMetadata {
+    /// E.g. "Completion of the office furniture tender".
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Unix epoch in milliseconds of the completion of the tender.
+    pub issued_at: Option<String>,
+    /// JSON with the details of the completed tender, e.g. the contract value.
+    pub extra: Option<String>,
+}
+
+/// The party of the completed tender a certificate is issued to.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum CertificateRole {
+    Supplier,
+    Issuer,
+}
+
+/// Certificate as stored by the contract.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Certificate {
+    pub owner_id: AccountId,
+    /// The tender the certificate was issued for.
+    pub tender_account_id: AccountId,
+    pub role: CertificateRole,
+    pub metadata: TokenMetadata,
+}
+
+/// Certificate as returned by the NEP-171 views.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonToken {
+    pub token_id: TokenId,
+    pub owner_id: AccountId,
+    pub tender_account_id: AccountId,
+    pub role: CertificateRole,
+    pub metadata: TokenMetadata,
+}
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Certificates {
+    /// The Tenderbox foundation role.
+    pub foundation: FoundationRole,
+
+    /// The sequence of the events emitted by the contract.
+    pub events: EventLog,
+
+    /// The account ID of the verify tender contract. Only verified tenders can mint certificates.
+    pub verify_tender_account_id: AccountId,
+
+    /// The metadata of the contract.
+    pub metadata: NFTContractMetadata,
+
+    /// The certificates by token ID.
+    pub tokens: UnorderedMap<TokenId, Certificate>,
+
+    /// The token IDs of the certificates of each owner.
+    pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+}
+
+/// External interface for the callbacks to self.
+#[ext_contract(ext_self)]
+pub trait ExtSelf {
+    fn on_tender_verified(
+        &mut self,
+        tender_account_id: AccountId,
+        supplier_account_id: AccountId,
+        issuer_account_id: AccountId,
+        metadata: TokenMetadata,
+    ) -> bool;
+}
+
+impl Default for Certificates {
+    fn default() -> Self {
+        Error::NotInitialized.panic()
+    }
+}
+
+#[near_bindgen]
+impl Certificates {
+    /// Initializes the contract with the given Tenderbox foundation account ID and the account ID
+    /// of the verify tender contract.
+    #[init]
+    pub fn new(foundation_account_id: AccountId, verify_tender_account_id: AccountId) -> Self {
+        require!(!env::state_exists(), Error::AlreadyInitialized);
+        require!(
+            env::is_valid_account_id(foundation_account_id.as_bytes()),
+            Error::InvalidFoundationAccountId
+        );
+        require!(
+            env::is_valid_account_id(verify_tender_account_id.as_bytes()),
+            Error::InvalidVerifyTenderAccountId
+        );
+        Self {
+            foundation: FoundationRole::new(foundation_account_id),
+            events: EventLog::new(),
+            verify_tender_account_id,
+            metadata: NFTContractMetadata {
+                spec: "nft-1.0.0".to_string(),
+                name: "Tenderbox Completion Certificates".to_string(),
+                symbol: "TBXC".to_string(),
+            },
+            tokens: UnorderedMap::new(StorageKey::CertificateTokens),
+            tokens_per_owner: LookupMap::new(StorageKey::CertificatesPerOwner),
+        }
+    }
+
+    /// Returns the account ID of the Tenderbox foundation.
+    pub fn get_foundation_account_id(&self) -> AccountId {
+        self.foundation.account_id().clone()
+    }
+
+    /// Returns the sequence number of the last event emitted by the contract, `0` if none was emitted yet.
+    pub fn get_last_event_seq(&self) -> u64 {
+        self.events.last_seq()
+    }
+
+    /// Returns the metadata of the contract (NEP-177).
+    pub fn nft_metadata(&self) -> NFTContractMetadata {
+        self.metadata.clone()
+    }
+
+    /// Returns the certificate with the given token ID, if any (NEP-171).
+    pub fn nft_token(&self, token_id: TokenId) -> Option<JsonToken> {
+        self.tokens
+            .get(&token_id)
+            .map(|certificate| Self::internal_json_token(token_id, certificate))
+    }
+
+    /// Returns the total number of certificates (NEP-181).
+    pub fn nft_total_supply(&self) -> U128 {
+        U128(self.tokens.len() as u128)
+    }
+
+    /// Returns up to `limit` certificates, starting at `from_index` (NEP-181).
+    pub fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonToken> {
+        let keys = self.tokens.keys_as_vector();
+        let values = self.tokens.values_as_vector();
+        let from_index = from_index.map(|index| index.0 as u64).unwrap_or(0);
+        let limit = limit.unwrap_or(keys.len());
+        (from_index..std::cmp::min(from_index + limit, keys.len()))
+            .map(|index| Self::internal_json_token(keys.get(index).unwrap(), values.get(index).unwrap()))
+            .collect()
+    }
+
+    /// Returns the number of certificates of the given account ID (NEP-181).
+    pub fn nft_supply_for_owner(&self, account_id: AccountId) -> U128 {
+        U128(
+            self.tokens_per_owner
+                .get(&account_id)
+                .map(|tokens| tokens.len() as u128)
+                .unwrap_or(0),
+        )
+    }
+
+    /// Returns up to `limit` certificates of the given account ID, starting at `from_index` (NEP-181).
+    pub fn nft_tokens_for_owner(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<JsonToken> {
+        let token_ids = match self.tokens_per_owner.get(&account_id) {
+            Some(token_ids) => token_ids,
+            None => return vec![],
+        };
+        let token_ids = token_ids.as_vector();
+        let from_index = from_index.map(|index| index.0 as u64).unwrap_or(0);
+        let limit = limit.unwrap_or(token_ids.len());
+        (from_index..std::cmp::min(from_index + limit, token_ids.len()))
+            .map(|index| {
+                let token_id = token_ids.get(index).unwrap();
+                let certificate = self.tokens.get(&token_id).unwrap();
+                Self::internal_json_token(token_id, certificate)
+            })
+            .collect()
+    }
+
+    /// Certificates are non-transferable.
+    #[payable]
+    pub fn nft_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<U64>,
+        memo: Option<String>,
+    ) {
+        let _ = (receiver_id, token_id, approval_id, memo);
+        Error::CertificatesNonTransferable.panic()
+    }
+
+    /// Certificates are non-transferable.
+    #[payable]
+    pub fn nft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<U64>,
+        memo: Option<String>,
+        msg: String,
+    ) {
+        let _ = (receiver_id, token_id, approval_id, memo, msg);
+        Error::CertificatesNonTransferable.panic()
+    }
+
+    /// Mints the completion certificates of the predecessor tender to its supplier and its issuer.
+    /// The certificates are only minted if the verify tender contract knows the tender.
+    /// This method is called by a tender once it is completed.
+    pub fn mint_certificates(
+        &mut self,
+        supplier_account_id: AccountId,
+        issuer_account_id: AccountId,
+        metadata: TokenMetadata,
+    ) -> Promise {
+        let tender_account_id = env::predecessor_account_id();
+        require!(
+            self.tokens
+                .get(&Self::internal_token_id(&tender_account_id, CertificateRole::Supplier))
+                .is_none(),
+            Error::CertificatesAlreadyMinted
+        );
+        ext_verify_tender::is_verified(
+            tender_account_id.clone(),
+            &self.verify_tender_account_id,
+            NO_DEPOSIT,
+            gas::verify_tender::IS_VERIFIED,
+        )
+        .then(ext_self::on_tender_verified(
+            tender_account_id,
+            supplier_account_id,
+            issuer_account_id,
+            metadata,
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            gas::CALLBACK,
+        ))
+    }
+
+    /// Callback after the verify tender contract was asked about the minting tender.
+    /// Mints the certificates and returns `true` if the tender is verified, returns `false` otherwise.
+    pub fn on_tender_verified(
+        &mut self,
+        tender_account_id: AccountId,
+        supplier_account_id: AccountId,
+        issuer_account_id: AccountId,
+        metadata: TokenMetadata,
+    ) -> bool {
+        assert_self();
+        let tender_verified = promise_result_as_bool();
+        if !tender_verified {
+            self.events.emit(
+                format!(
+                    "Not minting certificates for @{}: it is not a verified tender",
+                    tender_account_id
+                )
+                .as_bytes(),
+            );
+            return false;
+        }
+        // Another mint of the same tender may have completed while this one waited for the verify tender contract
+        if self
+            .tokens
+            .get(&Self::internal_token_id(&tender_account_id, CertificateRole::Supplier))
+            .is_some()
+        {
+            self.events.emit(
+                format!(
+                    "Not minting certificates for @{}: they are already minted",
+                    tender_account_id
+                )
+                .as_bytes(),
+            );
+            return false;
+        }
+        self.internal_mint(&tender_account_id, supplier_account_id, CertificateRole::Supplier, metadata.clone());
+        self.internal_mint(&tender_account_id, issuer_account_id, CertificateRole::Issuer, metadata);
+        true
+    }
+
+    /**************/
+    /* Tenderbox Foundation */
+    /**************/
+
+    /// Proposes the given account ID, e.g. a Sputnik DAO, as the new Tenderbox foundation account.
+    /// The role is only handed over once the proposed account calls `accept_foundation_role`,
+    /// which a DAO does through a function-call proposal.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn propose_foundation_account_id(&mut self, foundation_account_id: AccountId) {
+        self.assert_called_by_foundation();
+        self.foundation.propose(foundation_account_id);
+    }
+
+    /// Accepts the Tenderbox foundation role proposed to the predecessor.
+    /// This method can only be called by the account proposed with `propose_foundation_account_id`.
+    pub fn accept_foundation_role(&mut self) {
+        self.foundation.accept();
+        self.events.emit(
+            format!(
+                "The Tenderbox foundation role was handed over to @{}",
+                self.foundation.account_id()
+            )
+            .as_bytes(),
+        );
+    }
+
+    /************/
+    /* Internal */
+    /************/
+
+    /// Internal method to verify the predecessor was the Tenderbox Foundation account ID.
+    fn assert_called_by_foundation(&self) {
+        self.foundation.assert_called_by();
+    }
+
+    /// Internal method returning the token ID of the certificate of the given tender and role.
+    fn internal_token_id(tender_account_id: &AccountId, role: CertificateRole) -> TokenId {
+        match role {
+            CertificateRole::Supplier => format!("{}:supplier", tender_account_id),
+            CertificateRole::Issuer => format!("{}:issuer", tender_account_id),
+        }
+    }
+
+    /// Internal method to mint a certificate of the given tender to the given owner.
+    fn internal_mint(
+        &mut self,
+        tender_account_id: &AccountId,
+        owner_id: AccountId,
+        role: CertificateRole,
+        metadata: TokenMetadata,
+    ) {
+        let token_id = Self::internal_token_id(tender_account_id, role);
+        let mut token_ids = self.tokens_per_owner.get(&owner_id).unwrap_or_else(|| {
+            UnorderedSet::new(StorageKey::CertificatesOfOwner { owner_id: owner_id.clone() })
+        });
+        token_ids.insert(&token_id);
+        self.tokens_per_owner.insert(&owner_id, &token_ids);
+        self.tokens.insert(
+            &token_id,
+            &Certificate {
+                owner_id: owner_id.clone(),
+                tender_account_id: tender_account_id.clone(),
+                role,
+                metadata,
+            },
+        );
+        self.events.emit(format!("Minted the certificate {} to @{}", token_id, owner_id).as_bytes());
+        // The NEP-171 mint event, for the indexers and wallets listing the tokens of an account
+        let event = near_sdk::serde_json::json!({
+            "standard": "nep171",
+            "version": "1.0.0",
+            "event": "nft_mint",
+            "data": [{ "owner_id": owner_id, "token_ids": [token_id] }],
+        });
+        env::log(format!("EVENT_JSON:{}", event).as_bytes());
+    }
+
+    /// Internal method returning the NEP-171 view of the given certificate.
+    fn internal_json_token(token_id: TokenId, certificate: Certificate) -> JsonToken {
+        JsonToken {
+            token_id,
+            owner_id: certificate.owner_id,
+            tender_account_id: certificate.tender_account_id,
+            role: certificate.role,
+            metadata: certificate.metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::{testing_env, MockedBlockchain, PromiseResult};
+
+    mod test_utils;
+    use test_utils::*;
+
+    fn metadata() -> TokenMetadata {
+        TokenMetadata {
+            title: Some("Completion of the office furniture tender".to_string()),
+            description: None,
+            issued_at: Some("1633046400000".to_string()),
+            extra: None,
+        }
+    }
+
+    #[test]
+    fn test_mint_certificates() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_certificates())
+            .predecessor_account_id(account_tender())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = Certificates::new(account_tenderbox(), account_verify_tender());
+        contract.mint_certificates(account_supplier(), account_tender_owner(), metadata());
+
+        context.predecessor_account_id = account_certificates();
+        testing_env_with_promise_results(context.clone(), PromiseResult::Successful(b"true".to_vec()));
+        assert!(contract.on_tender_verified(
+            account_tender(),
+            account_supplier(),
+            account_tender_owner(),
+            metadata()
+        ));
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.nft_total_supply().0, 2);
+        let certificates = contract.nft_tokens_for_owner(account_supplier(), None, None);
+        assert_eq!(certificates.len(), 1);
+        assert_eq!(certificates[0].tender_account_id, account_tender());
+        assert_eq!(certificates[0].role, CertificateRole::Supplier);
+        assert_eq!(contract.nft_supply_for_owner(account_tender_owner()).0, 1);
+    }
+
+    #[test]
+    fn test_mint_certificates_twice() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_certificates())
+            .predecessor_account_id(account_tender())
+            .finish();
+        testing_env!(context.clone());
+
+        // Both mints pass the check before the verify tender contract answers
+        let mut contract = Certificates::new(account_tenderbox(), account_verify_tender());
+        contract.mint_certificates(account_supplier(), account_tender_owner(), metadata());
+        contract.mint_certificates(account_supplier(), account_tender_owner(), metadata());
+
+        context.predecessor_account_id = account_certificates();
+        testing_env_with_promise_results(context.clone(), PromiseResult::Successful(b"true".to_vec()));
+        assert!(contract.on_tender_verified(
+            account_tender(),
+            account_supplier(),
+            account_tender_owner(),
+            metadata()
+        ));
+        assert!(near_sdk::test_utils::get_logs().iter().any(|log| log.starts_with("EVENT_JSON:")
+            && log.contains("\"event\":\"nft_mint\"")
+            && log.contains(&format!("\"owner_id\":\"{}\"", account_supplier()))));
+
+        // The second callback leaves the minted certificates as they are
+        assert!(!contract.on_tender_verified(
+            account_tender(),
+            account_tender_owner(),
+            account_supplier(),
+            metadata()
+        ));
+        assert_eq!(contract.nft_total_supply().0, 2);
+        assert_eq!(contract.nft_tokens_for_owner(account_supplier(), None, None)[0].role, CertificateRole::Supplier);
+    }
+
+    #[test]
+    fn test_mint_certificates_unverified_tender() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_certificates())
+            .predecessor_account_id(account_certificates())
+            .finish();
+        testing_env_with_promise_results(context.clone(), PromiseResult::Successful(b"false".to_vec()));
+
+        let mut contract = Certificates::new(account_tenderbox(), account_verify_tender());
+        assert!(!contract.on_tender_verified(
+            account_tender(),
+            account_supplier(),
+            account_tender_owner(),
+            metadata()
+        ));
+        assert_eq!(contract.nft_total_supply().0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "E513: Completion certificates are non-transferable")]
+    fn test_transfer_certificate() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_certificates())
+            .predecessor_account_id(account_supplier())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = Certificates::new(account_tenderbox(), account_verify_tender());
+        contract.nft_transfer(account_tokens_owner(), format!("{}:supplier", account_tender()), None, None);
+    }
+}
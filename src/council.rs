@@ -0,0 +1,116 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{Base64VecU8, U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, AccountId};
+
+use crate::errors::*;
+use crate::{FeeDiscountTier, GasConfig, OperatorFeeSplit};
+
+// The optional council of the Tender Factory. Once set, sensitive factory operations are only
+// executed after M of its N members confirmed a proposal.
+
+/// The time a council proposal can collect confirmations before it expires. 7 days.
+pub const PROPOSAL_EXPIRY: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
+/// Members of the council and the number of confirmations a proposal needs.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Council {
+    /// Account IDs of the council members.
+    pub members: Vec<AccountId>,
+    /// Number of member confirmations needed to execute a proposal.
+    pub threshold: u32,
+}
+
+impl Council {
+    /// Asserts the members are valid and unique account IDs and the threshold can be reached.
+    pub fn assert_valid(&self) {
+        for (index, member) in self.members.iter().enumerate() {
+            require!(
+                env::is_valid_account_id(member.as_bytes()),
+                Error::InvalidCouncilMemberAccountId
+            );
+            require!(!self.members[..index].contains(member), Error::DuplicateCouncilMembers);
+        }
+        require!(
+            self.threshold > 0 && self.threshold as usize <= self.members.len(),
+            Error::InvalidCouncilThreshold
+        );
+    }
+
+    /// Returns `true` if the given account ID is a member of the council.
+    pub fn is_member(&self, account_id: &AccountId) -> bool {
+        self.members.contains(account_id)
+    }
+}
+
+/// Sensitive factory operation that needs the council's confirmation.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub enum CouncilAction {
+    /// Approves the factory code with the given sha256 hash to be deployed with `upgrade`.
+    UpgradeFactory { code_hash: Base64VecU8 },
+    /// Announces a new minimum deposit to attach to `create_tender`, in effect after the timelock delay.
+    SetMinAttachedBalance { min_attached_balance: U128 },
+    /// Announces a new posting fee kept from the deposit of `create_tender`, in effect after the timelock delay.
+    SetPostingFee { posting_fee: U128 },
+    /// Announces a new protocol treasury receiving the posting fees, in effect after the timelock delay.
+    SetTreasuryAccount { treasury_account_id: AccountId },
+    /// Announces a new split of the posting fee with the factory operator, or its removal,
+    /// in effect after the timelock delay.
+    SetOperatorFeeSplit { operator_fee_split: Option<OperatorFeeSplit> },
+    /// Announces new gas allowances for the factory's cross-contract calls, in effect after the timelock delay.
+    SetGasConfig { gas_config: GasConfig },
+    /// Announces new posting fee discount tiers for repeat issuers, in effect after the timelock delay.
+    SetFeeDiscountTiers { fee_discount_tiers: Vec<FeeDiscountTier> },
+    /// Announces a new retention period of closed tenders before `archive_closed_tenders` archives them,
+    /// in nanoseconds, in effect after the timelock delay.
+    SetArchiveRetention { archive_retention: U64 },
+    /// Announces a new verify tender contract, in effect after the timelock delay.
+    SetVerifyTenderAccount { verify_tender_account_id: AccountId },
+    /// Announces a new parameter registry, or its removal to fall back to the factory's own parameters,
+    /// in effect after the timelock delay.
+    SetParameterRegistry { parameter_registry_account_id: Option<AccountId> },
+    /// Announces a new timelock delay in nanoseconds, in effect after the current timelock delay.
+    SetTimelockDelay { timelock_delay: U64 },
+    /// Cancels the configuration changes that are not in effect yet.
+    CancelPendingConfig,
+    /// Stops the creation of new tenders.
+    Pause,
+    /// Resumes the creation of new tenders.
+    Unpause,
+    /// Replaces the council, or removes it to hand the operations back to the foundation.
+    /// Pending proposals are dropped.
+    SetCouncil { council: Option<Council> },
+}
+
+/// Proposal collecting the confirmations of the council members.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Proposal {
+    /// The council member that proposed the action.
+    pub proposer: AccountId,
+    /// The action executed once the proposal is confirmed.
+    pub action: CouncilAction,
+    /// The council members that confirmed the proposal, including the proposer.
+    pub confirmations: Vec<AccountId>,
+    /// The block timestamp in nanoseconds from which the proposal can no longer be confirmed.
+    pub expires_at: u64,
+}
+
+impl Proposal {
+    /// Returns `true` if the proposal can no longer be confirmed.
+    pub fn is_expired(&self) -> bool {
+        env::block_timestamp() >= self.expires_at
+    }
+}
+
+/// Pending proposal returned by the views.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProposalView {
+    pub id: u64,
+    pub proposer: AccountId,
+    pub action: CouncilAction,
+    pub confirmations: Vec<AccountId>,
+    pub expires_at: U64,
+}
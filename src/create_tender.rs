@@ -1,42 +1,110 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{self, UnorderedSet, UnorderedMap};
-use near_sdk::json_types::{Base58PublicKey, Base64VecU8, U128};
+use near_sdk::collections::{self, LazyOption, LookupMap, UnorderedSet, UnorderedMap};
+use near_sdk::json_types::{Base58PublicKey, Base64VecU8, U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
-use near_sdk::{env, ext_contract, near_bindgen, AccountId, Balance, Promise, PromiseOrValue};
+use near_sdk::{env, ext_contract, near_bindgen, AccountId, Balance, Gas, Promise, PromiseOrValue, PromiseResult};
 
 
-#[global_allocator]
-static ALLOC: near_sdk::wee_alloc::WeeAlloc<'_> = near_sdk::wee_alloc::WeeAlloc::INIT;
-
 //Implemented below is the Tender Factory Implementation and the process of sending NEAR to commit onto implementing a tender issued by a small business or merchant
 
+#[macro_use]
+mod errors;
+use crate::errors::*;
+
+mod foundation;
+use crate::foundation::*;
+
 mod utils;
-use crate::utils::*
+use crate::utils::*;
+
+mod council;
+use crate::council::*;
+
+mod timelock;
+use crate::timelock::*;
+
+mod types;
+use crate::types::*;
+
+mod interfaces;
+use crate::interfaces::*;
+
+mod events;
+use crate::events::*;
+
+mod storage;
+use crate::storage::*;
 
 // Estimating that it will require at least 30 NEAR tokens to store a single tender, could still change(Issue)
+// Initial value of the minimum attached balance, which the council (or the foundation) can change afterwards.
 const MIN_ATTACHED_BALANCE: Balance = 30_000_000_000_000_000_000_000_000;
 
-// Feature to include, a helper function to calculate storage cost of a tender created before hand and then price how much it would cost to issue/post a tender
+// Initial posting fee discount of repeat issuers: 10% once they completed 5 tenders.
+const DEFAULT_FEE_DISCOUNT_TIER: FeeDiscountTier = FeeDiscountTier {
+    min_completed_tenders: 5,
+    discount_bps: 1_000,
+};
+
+// Initial time closed tenders are kept before `archive_closed_tenders` archives them. 90 days.
+const DEFAULT_ARCHIVE_RETENTION: u64 = 90 * 24 * 60 * 60 * 1_000_000_000;
+
+// Whether the bundled tender template exposes `new_borsh`, taking its `TenderParameters` Borsh-encoded.
+// The factory then initializes new tenders with Borsh instead of JSON arguments, which is cheaper in gas.
+const TENDER_BORSH_INIT: bool = false;
 
+// Code of the tenders the factory deploys. Its hash is recorded on verification, so a tender running
+// other code can be detected later.
+const TENDER_CODE: &[u8] = include_bytes!("../../tender/res/tender.wasm");
+
+// Feature to include, a helper function to calculate storage cost of a tender created before hand and then price how much it would cost to issue/post a tender
 
 
+/// Initial gas allowances of the factory, see `GasConfig`.
 pub mod gas {
     use near_sdk::Gas;
 
-    /// The base amount of gas for a regular execution.
-    const BASE: Gas = 25_000_000_000_000;
+    use crate::interfaces::gas::BASE;
 
-    /// The amount of Gas the contract will attach to the promise to create the staking pool.
-    /// The base for the execution and the base for staking action to verify the staking key.
-    pub const STAKING_POOL_NEW: Gas = BASE * 2;
+    /// The amount of Gas the contract will attach to the promise initializing the new tender.
+    /// The base for the execution and the base for storing the tender parameters.
+    pub const TENDER_NEW: Gas = BASE * 2;
 
     /// The amount of Gas the contract will attach to the callback to itself.
-    /// The base for the execution and the base for whitelist call or cash rollback.
-    pub const CALLBACK: Gas = BASE * 2;
+    /// The base for the execution, the base for whitelist call or cash rollback and the base for
+    /// forwarding the posting fee to the treasury.
+    pub const CALLBACK: Gas = BASE * 3;
 
     /// The amount of Gas the contract will attach to the promise to the verifying tender contract(borrows the concept of whitelisting staking pool contracts.
     /// The base for the execution.
     pub const VERIFY_TENDER: Gas = BASE;
+
+    /// The amount of Gas the contract will attach to the promise forwarding the posting fee to the treasury.
+    /// The base for the execution.
+    pub const TREASURY_DEPOSIT: Gas = BASE;
+
+    /// The amount of Gas the contract will attach to the callback caching the circuit breaker state.
+    /// The base for the execution.
+    pub const CIRCUIT_BREAKER_CALLBACK: Gas = BASE;
+
+    /// The amount of Gas the contract will attach to the promise to the parameter registry.
+    /// The base for the execution.
+    pub const PARAMETER_REGISTRY: Gas = BASE;
+
+    /// The amount of Gas the contract will attach to the callback caching the platform parameters.
+    /// The base for the execution.
+    pub const PARAMETERS_CALLBACK: Gas = BASE;
+
+    /// The amount of Gas the contract will attach to the promise deleting an archived tender account.
+    /// The base for the execution.
+    pub const TENDER_DELETE: Gas = BASE;
+
+    /// The amount of Gas the contract will attach to the callback archiving a deleted tender.
+    /// The base for the execution.
+    pub const TENDER_DELETED_CALLBACK: Gas = BASE;
+
+    /// The amount of Gas the factory will attach to the migration of its state after an upgrade.
+    /// The base for the execution.
+    pub const MIGRATE: Gas = BASE;
 }
 
 #[near_bindgen]
@@ -47,207 +115,2005 @@ pub struct TenderFactory {
 
     /// Account ID of the Verify Tender Contract.
     /// The verify account implementation mimics the idea of the whiteli    ///st contract with a few alterations
-    verify_tender_account_id: AccountId,
+    verify_tender_account_id: Timelocked<AccountId>,
+
+    /// Account ID of the tenders that were finalized and deleted their accounts after closure
+    archived_tender_account_ids: UnorderedSet<AccountId>,
+
+    /// Creation record of each tender, kept for the indexers bootstrapping from `dump_tenders`
+    tender_records: LookupMap<AccountId, VersionedTenderRecord>,
+
+    /// The Tenderbox foundation role. It manages the factory wide configuration.
+    foundation: FoundationRole,
+
+    /// Reusable milestone/payment-schedule templates published by the foundation, by template ID
+    milestone_templates: UnorderedMap<u64, VersionedMilestoneTemplate>,
 
+    /// The ID the next published milestone template gets
+    next_milestone_template_id: u64,
+
+    /// Sequence of the events emitted by the factory
+    events: EventLog,
+
+    /// Minimum amount of tokens to attach to `create_tender`
+    min_attached_balance: Timelocked<Balance>,
+
+    /// The delay configuration changes wait before taking effect, in nanoseconds
+    timelock_delay: Timelocked<u64>,
+
+    /// Part of the attached deposit kept as posting fee and forwarded to the treasury once the tender is created
+    posting_fee: Timelocked<Balance>,
+
+    /// Account ID of the protocol treasury receiving the posting fees
+    treasury_account_id: Timelocked<Option<AccountId>>,
+
+    /// Split of the posting fee with the third party operating the factory, if any
+    operator_fee_split: LazyTimelocked<Option<OperatorFeeSplit>>,
+
+    /// Gas the factory attaches to its cross-contract calls
+    gas_config: LazyTimelocked<GasConfig>,
+
+    /// Cached state of the platform-wide circuit breaker of the Verify Tender contract
+    circuit_breaker_tripped: bool,
+
+    /// Account ID of the parameter registry. Once set, its cached parameters take precedence over the local ones.
+    parameter_registry_account_id: Timelocked<Option<AccountId>>,
+
+    /// Platform parameters cached by `refresh_parameters`, with the parameter registry they come from
+    cached_parameters: LazyOption<(AccountId, PlatformParameters)>,
+
+    /// Whether the creation of new tenders is paused
+    paused: bool,
+
+    /// The optional council. Once set, sensitive operations need the confirmation of its members.
+    council: LazyOption<Council>,
+
+    /// Pending council proposals, by proposal ID
+    council_proposals: UnorderedMap<u64, Proposal>,
+
+    /// The ID the next council proposal gets
+    next_proposal_id: u64,
+
+    /// The sha256 hash of the factory code approved to be deployed with `upgrade`
+    approved_code_hash: Option<Vec<u8>>,
+
+    /// Posting fee discounts of issuers by number of completed tenders
+    fee_discount_tiers: LazyTimelocked<Vec<FeeDiscountTier>>,
+
+    /// Number of tenders each issuer completed, by the account ID that created them
+    completed_tenders_by_issuer: LookupMap<AccountId, u64>,
+
+    /// Promo codes reducing the posting fee, by the sha256 hash of the code
+    promo_codes: LookupMap<Vec<u8>, PromoCode>,
+
+    /// Creation credit funded by sponsors, by the account ID of the issuer it is for
+    sponsor_credits: LookupMap<AccountId, SponsorCredit>,
+
+    /// Tenders that reported their closure and wait for the retention period before being archived
+    closed_tenders: UnorderedMap<AccountId, ClosedTender>,
+
+    /// Compact records replacing the creation records of the archived tenders
+    archived_tenders: LookupMap<AccountId, ArchivedTender>,
+
+    /// The time closed tenders are kept before `archive_closed_tenders` archives them, in nanoseconds
+    archive_retention: Timelocked<u64>,
+
+    /// The index in the closed tenders `archive_closed_tenders` resumes its walk from
+    archive_cursor: u64,
 }
 
 impl Default for TenderFactory {
     fn default() -> Self {
-        env::panic(b"The contract should be initialized before usage")
+        Error::NotInitialized.panic()
+    }
+}
+
+/// Stored layouts of the factory state. The state is stored without a version tag, so `migrate`
+/// reads it in the newest layout it matches: when the fields change, keep the previous layout as
+/// its own struct, add a variant holding it and convert it in `From`, like `VersionedTenderRecord`.
+pub enum VersionedState {
+    V1(TenderFactory),
+}
+
+impl VersionedState {
+    /// Reads the stored state in the newest layout it matches.
+    fn read() -> Self {
+        let state = env::storage_read(b"STATE").unwrap_or_else(|| Error::NotInitialized.panic());
+        TenderFactory::try_from_slice(&state)
+            .map(VersionedState::V1)
+            .unwrap_or_else(|_| Error::UnknownStateLayout.panic())
+    }
+}
+
+impl From<VersionedState> for TenderFactory {
+    fn from(state: VersionedState) -> Self {
+        match state {
+            VersionedState::V1(state) => state,
+        }
+    }
+}
+
+
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MilestoneTemplate {
+    // Name of the template, e.g. "30/40/30 goods delivery"
+    name: String,
+    // Milestones of the template in the order they are reached
+    milestones: Vec<Milestone>,
+}
+
+
+/// Stored versions of the milestone templates, see `VersionedTenderRecord`
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum VersionedMilestoneTemplate {
+    V1(MilestoneTemplate),
+}
+
+impl From<VersionedMilestoneTemplate> for MilestoneTemplate {
+    fn from(template: VersionedMilestoneTemplate) -> Self {
+        match template {
+            VersionedMilestoneTemplate::V1(template) => template,
+        }
+    }
+}
+
+impl From<MilestoneTemplate> for VersionedMilestoneTemplate {
+    fn from(template: MilestoneTemplate) -> Self {
+        VersionedMilestoneTemplate::V1(template)
+    }
+}
+
+
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OperatorFeeSplit {
+    // Account ID of the third party operating the factory
+    operator_account_id: AccountId,
+    // Share of the posting fee paid to the operator, in basis points. The rest goes to the Tenderbox foundation.
+    operator_fee_bps: u16,
+}
+
+impl OperatorFeeSplit {
+    /// Splits the given posting fee into the operator share and the foundation share.
+    /// The operator share is rounded down, so the foundation keeps the remainder.
+    pub fn split(&self, posting_fee: Balance) -> (Balance, Balance) {
+        let operator_share = posting_fee * Balance::from(self.operator_fee_bps) / 10_000;
+        (operator_share, posting_fee - operator_share)
+    }
+}
+
+
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeDiscountTier {
+    // Number of completed tenders from which an issuer gets the discount
+    min_completed_tenders: u64,
+    // Discount on the posting fee, in basis points
+    discount_bps: u16,
+}
+
+
+/// Posting fee reduction issuers get by passing the promo code to `create_tender`.
+/// Only the hash of the code is stored, so the code can't be read from the factory state.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PromoCode {
+    // Reduction of the posting fee, in basis points. 10000 waives the fee.
+    discount_bps: u16,
+    // Number of tenders that can still be created with the code
+    remaining_uses: u32,
+    // Block timestamp in nanoseconds after which the code can't be used
+    expires_at: U64,
+}
+
+
+/// Creation credit sponsors funded for an issuer. It covers what the deposit of the issuer's
+/// `create_tender` lacks, e.g. NGOs subsidizing the tenders of small merchants.
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SponsorCredit {
+    /// The sponsors of the issuer, with the credit each of them has left.
+    pub sponsors: Vec<(AccountId, Balance)>,
+}
+
+impl SponsorCredit {
+    /// Returns the total credit of the issuer.
+    pub fn amount(&self) -> Balance {
+        self.sponsors.iter().map(|(_, amount)| amount).sum()
+    }
+
+    /// Adds the given amount to the credit of the given sponsor.
+    pub fn add(&mut self, sponsor_account_id: AccountId, amount: Balance) {
+        match self.sponsors.iter_mut().find(|(account_id, _)| *account_id == sponsor_account_id) {
+            Some((_, credit)) => *credit += amount,
+            None => self.sponsors.push((sponsor_account_id, amount)),
+        }
+    }
+
+    /// Draws the given amount from the sponsors, in the order they funded the credit.
+    /// Returns the amount drawn from each sponsor.
+    pub fn draw(&mut self, mut amount: Balance) -> Vec<(AccountId, Balance)> {
+        let mut draws = vec![];
+        for (sponsor_account_id, credit) in self.sponsors.iter_mut() {
+            if amount == 0 {
+                break;
+            }
+            let drawn = std::cmp::min(*credit, amount);
+            *credit -= drawn;
+            amount -= drawn;
+            draws.push((sponsor_account_id.clone(), drawn));
+        }
+        self.sponsors.retain(|(_, credit)| *credit > 0);
+        draws
+    }
+
+    /// Gives the given draws back to their sponsors, at the position they had before `draw`.
+    pub fn restore(&mut self, draws: Vec<(AccountId, Balance)>) {
+        // `draw` takes from the front, so the sponsors it used up go back to the front, in order
+        for (sponsor_account_id, amount) in draws.into_iter().rev() {
+            match self.sponsors.iter_mut().find(|(account_id, _)| *account_id == sponsor_account_id) {
+                Some((_, credit)) => *credit += amount,
+                None => self.sponsors.insert(0, (sponsor_account_id, amount)),
+            }
+        }
+    }
+}
+
+
+/// Gas the factory attaches to its cross-contract calls. Measured costs change, e.g. the
+/// initialization of a tender as `TenderParameters` grows, so they are kept in state.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct GasConfig {
+    /// Gas attached to the initialization of a new tender, unless the parameter registry sets one
+    pub tender_new: U64,
+    /// Gas attached to the callback of `create_tender`
+    pub callback: U64,
+    /// Gas attached to the calls to the Verify Tender contract
+    pub verify_tender: U64,
+    /// Gas attached to the posting fee deposit to the treasury
+    pub treasury_deposit: U64,
+    /// Gas attached to the callback caching the circuit breaker state
+    pub circuit_breaker_callback: U64,
+    /// Gas attached to the calls to the parameter registry
+    pub parameter_registry: U64,
+    /// Gas attached to the callback caching the platform parameters
+    pub parameters_callback: U64,
+    /// Gas attached to the deletion of archived tender accounts
+    pub tender_delete: U64,
+    /// Gas attached to the callback archiving a deleted tender
+    pub tender_deleted_callback: U64,
+}
+
+impl Default for GasConfig {
+    fn default() -> Self {
+        Self {
+            tender_new: gas::TENDER_NEW.into(),
+            callback: gas::CALLBACK.into(),
+            verify_tender: gas::VERIFY_TENDER.into(),
+            treasury_deposit: gas::TREASURY_DEPOSIT.into(),
+            circuit_breaker_callback: gas::CIRCUIT_BREAKER_CALLBACK.into(),
+            parameter_registry: gas::PARAMETER_REGISTRY.into(),
+            parameters_callback: gas::PARAMETERS_CALLBACK.into(),
+            tender_delete: gas::TENDER_DELETE.into(),
+            tender_deleted_callback: gas::TENDER_DELETED_CALLBACK.into(),
+        }
+    }
+}
+
+impl GasConfig {
+    /// Asserts every allowance is positive.
+    pub fn assert_valid(&self) {
+        require!(
+            [
+                self.tender_new,
+                self.callback,
+                self.verify_tender,
+                self.treasury_deposit,
+                self.circuit_breaker_callback,
+                self.parameter_registry,
+                self.parameters_callback,
+                self.tender_delete,
+                self.tender_deleted_callback,
+            ]
+            .iter()
+            .all(|gas| gas.0 > 0),
+            Error::GasAllowanceNotPositive
+        );
+    }
+}
+
+
+/// Arguments of `create_tender`, passed Borsh-encoded to `create_tender_borsh`
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct CreateTenderArgs {
+    pub tender_registration_id: String,
+    pub owner_id: AccountId,
+    pub tender_public_key: Base58PublicKey,
+    pub tender_proposal: String,
+    pub line_items: Vec<LineItem>,
+    pub industry: String,
+    pub delivery_points: Vec<DeliveryPoint>,
+    pub criteria: EvaluationCriteria,
+    pub emergency: bool,
+    pub milestone_template_id: Option<u64>,
+    pub promo_code: Option<String>,
+    pub opens_at_ns: Option<U64>,
+}
+
+
+/// Parameters replacing those of the source tender in `repost_tender`. The ones left out are copied.
+#[derive(Deserialize, Serialize, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TenderOverrides {
+    pub owner_id: Option<AccountId>,
+    pub tender_public_key: Option<Base58PublicKey>,
+    pub tender_proposal: Option<String>,
+    pub line_items: Option<Vec<LineItem>>,
+    pub industry: Option<String>,
+    pub delivery_points: Option<Vec<DeliveryPoint>>,
+    pub criteria: Option<EvaluationCriteria>,
+    pub emergency: Option<bool>,
+    /// Template replacing the milestones of the source tender
+    pub milestone_template_id: Option<u64>,
+    pub promo_code: Option<String>,
+    /// Opening time of the new tender. The opening time of the source tender is not copied.
+    pub opens_at_ns: Option<U64>,
+}
+
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TenderRecord {
+    // Parameters the tender was initialized with
+    parameters: TenderParameters,
+    // Account ID that called `create_tender` and paid the deposit
+    creator_account_id: AccountId,
+    // Posting fee kept from the deposit
+    posting_fee: Balance,
+    // Block timestamp in nanoseconds of the `create_tender` call
+    created_at: u64,
+    // Whether the tender account was created and initialized
+    created: bool,
+}
+
+
+/// Stored versions of the tender records. Records are upgraded to the current `TenderRecord` when
+/// read and stored as the latest version when written, so a new layout doesn't need a migration of
+/// every record: add a variant holding the new layout and convert the previous ones in `From`.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum VersionedTenderRecord {
+    V1(TenderRecord),
+}
+
+impl From<VersionedTenderRecord> for TenderRecord {
+    fn from(record: VersionedTenderRecord) -> Self {
+        match record {
+            VersionedTenderRecord::V1(record) => record,
+        }
     }
 }
 
+impl From<TenderRecord> for VersionedTenderRecord {
+    fn from(record: TenderRecord) -> Self {
+        VersionedTenderRecord::V1(record)
+    }
+}
+
+
+/// Lifecycle state of a tender, as far as the factory knows it
+#[derive(Serialize, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum TenderStatus {
+    /// The tender account is being created
+    Creating,
+    /// The tender was created but doesn't accept bids before its opening time
+    Scheduled,
+    /// The tender was created and runs on its own account
+    Active,
+    /// The tender reported its closure and waits for the retention period before being archived
+    Closed,
+}
+
+
+/// Tender that reported its closure with `on_tender_closed`
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ClosedTender {
+    // How the tender ended
+    outcome: TenderOutcome,
+    // Block timestamp in nanoseconds of the closure
+    closed_at: u64,
+    // Whether the tender opted in to the deletion of its account once archived
+    delete_on_archive: bool,
+    // Whether the deletion of its account was requested and waits for `on_tender_deleted`
+    deleting: bool,
+}
+
 
-pub struct TenderParameters {
-    // Owner account ID of the tender issued
+/// Compact record of an archived tender, kept once its creation record is dropped.
+/// Returned by `get_archived_tenders`, so the history of the factory outlives the tender accounts.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ArchivedTender {
+    // Account ID of the tender owner
     owner_id: AccountId,
-    // Public key initiated to secure the tender
-    tender_public_key: Base58PublicKey,
-    // Tender proposal statement
-    tender_proposal: String,
-    // Product/service needed
-    product: String,
-    // Unit price of product/service needed
-    unitproductprice: U128,
-    // Quantity of product/service needed
-    quantityproduct: u64,
-    // Industry/Sector of the Tender originator
-    industry: String,
-    // Location of Delivery for the Product/Service
-    location: String,
+    // Account ID that called `create_tender`, receiving the residual balance of deleted tender accounts
+    creator_account_id: AccountId,
+    // How the tender ended, with the winner and the final amount of completed tenders
+    outcome: TenderOutcome,
+    // Block timestamp in nanoseconds of the `create_tender` call
+    created_at: U64,
+    // Block timestamp in nanoseconds of the completion or cancellation
+    closed_at: U64,
+}
+
+
+/// Complete state of a tender returned by `dump_tenders`
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TenderDump {
+    tender_account_id: AccountId,
+    status: TenderStatus,
+    parameters: TenderParameters,
+    creator_account_id: AccountId,
+    posting_fee: U128,
+    created_at: U64,
+}
+
+
+/// Configuration changes of the factory that were announced but are not in effect yet
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FactoryPendingConfig {
+    min_attached_balance: Option<PendingChange<U128>>,
+    posting_fee: Option<PendingChange<U128>>,
+    treasury_account_id: Option<PendingChange<Option<AccountId>>>,
+    operator_fee_split: Option<PendingChange<Option<OperatorFeeSplit>>>,
+    gas_config: Option<PendingChange<GasConfig>>,
+    fee_discount_tiers: Option<PendingChange<Vec<FeeDiscountTier>>>,
+    archive_retention: Option<PendingChange<U64>>,
+    verify_tender_account_id: Option<PendingChange<AccountId>>,
+    parameter_registry_account_id: Option<PendingChange<Option<AccountId>>>,
+    timelock_delay: Option<PendingChange<U64>>,
 }
 
 
 /// External interface for the callbacks to self
 #[ext_contract(ext_self)]
 pub trait ExtSelf {
-    fn create_tender(
-       &mut self,
-       tender_account_id: AccountId,
-       posting_fee: U128,
-       predecessor_account_id: AccountId,
-    ) -> Promise;
-}
+    fn on_tender_create(
+        &mut self,
+        tender_account_id: AccountId,
+        attached_deposit: U128,
+        posting_fee: U128,
+        predecessor_account_id: AccountId,
+        promo_code_hash: Option<Base64VecU8>,
+        sponsor_draws: Vec<(AccountId, U128)>,
+    ) -> bool;
+
+    fn on_tender_reserved(
+        &mut self,
+        tender_account_id: AccountId,
+        attached_deposit: U128,
+        posting_fee: U128,
+        predecessor_account_id: AccountId,
+        promo_code_hash: Option<Base64VecU8>,
+        sponsor_draws: Vec<(AccountId, U128)>,
+    ) -> bool;
 
+    fn on_circuit_breaker_refreshed(&mut self) -> bool;
 
-/// External interface for the Verify Tender(whitelist) contract.
-pub trait ExVerifyTender {
-    fn add_tender(&mut self, tender_account_id: AccountId) -> bool;
+    fn on_parameters_refreshed(&mut self, parameter_registry_account_id: AccountId) -> bool;
+
+    fn on_tender_deleted(&mut self, tender_account_id: AccountId) -> bool;
 }
 
 
 #[near_bindgen]
 impl TenderFactory {
      /// Initializes the tender factory with the given account ID of the    ///Verify tender(whitelist) contract
+     /// and the account ID of the Tenderbox foundation.
      #[init]
-     pub fn new(verify_tender_account_id: AccountId) -> Self {
-     	 assert!(!env::state_exists(), "The contract is already initialized");
-	 assert!(
-	     env::is_valid_account_id(verify_tender_account_id.as_bytes()), "The verify tender account ID is invalid");
+     pub fn new(verify_tender_account_id: AccountId, foundation_account_id: AccountId) -> Self {
+     	 require!(!env::state_exists(), Error::AlreadyInitialized);
+	 require!(
+	     env::is_valid_account_id(verify_tender_account_id.as_bytes()),
+	     Error::InvalidVerifyTenderAccountId
+	 );
+	 require!(
+	     env::is_valid_account_id(foundation_account_id.as_bytes()),
+	     Error::InvalidFoundationAccountId
+	 );
 	     Self {
-	         verify_tender_account_id,
-		 tender_account_ids: UnorderedSet::new(b"s".to_vec()),
+	         verify_tender_account_id: Timelocked::new(verify_tender_account_id),
+		 tender_account_ids: UnorderedSet::new(StorageKey::Tenders),
+		 archived_tender_account_ids: UnorderedSet::new(StorageKey::ArchivedTenders),
+		 tender_records: LookupMap::new(StorageKey::TenderRecords),
+		 foundation: FoundationRole::new(foundation_account_id),
+		 milestone_templates: UnorderedMap::new(StorageKey::MilestoneTemplates),
+		 next_milestone_template_id: 0,
+		 events: EventLog::new(),
+		 min_attached_balance: Timelocked::new(MIN_ATTACHED_BALANCE),
+		 timelock_delay: Timelocked::new(DEFAULT_TIMELOCK_DELAY),
+		 posting_fee: Timelocked::new(0),
+		 treasury_account_id: Timelocked::new(None),
+		 operator_fee_split: LazyTimelocked::new(StorageKey::FactoryOperatorFeeSplit, None),
+		 gas_config: LazyTimelocked::new(StorageKey::FactoryGasConfig, GasConfig::default()),
+		 circuit_breaker_tripped: false,
+		 parameter_registry_account_id: Timelocked::new(None),
+		 cached_parameters: LazyOption::new(StorageKey::FactoryCachedParameters, None),
+		 paused: false,
+		 council: LazyOption::new(StorageKey::FactoryCouncil, None),
+		 council_proposals: UnorderedMap::new(StorageKey::CouncilProposals),
+		 next_proposal_id: 0,
+		 approved_code_hash: None,
+		 fee_discount_tiers: LazyTimelocked::new(StorageKey::FactoryFeeDiscountTiers, vec![DEFAULT_FEE_DISCOUNT_TIER]),
+		 completed_tenders_by_issuer: LookupMap::new(StorageKey::CompletedTendersByIssuer),
+		 promo_codes: LookupMap::new(StorageKey::PromoCodes),
+		 sponsor_credits: LookupMap::new(StorageKey::SponsorCredits),
+		 closed_tenders: UnorderedMap::new(StorageKey::ClosedTenders),
+		 archived_tenders: LookupMap::new(StorageKey::ArchivedTenderRecords),
+		 archive_retention: Timelocked::new(DEFAULT_ARCHIVE_RETENTION),
+		 archive_cursor: 0,
 	     }
      }
 
+    /// Migrates the stored state to the current layout, see `VersionedState`.
+    /// This method can only be called by the factory itself, right after `upgrade` deployed the new code.
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        assert_self();
+        VersionedState::read().into()
+    }
+
+
+    /// Returns the account ID of the Tenderbox foundation.
+    pub fn get_foundation_account_id(&self) -> AccountId {
+        self.foundation.account_id().clone()
+    }
+
+    /// Returns the sequence number of the last event emitted by the factory, `0` if none was emitted yet
+    pub fn get_last_event_seq(&self) -> u64 {
+        self.events.last_seq()
+    }
 
      /// Returns the minimum amount of tokens needed to attach to the fu    ///nction call to create a new tender.
     pub fn get_min_attached_balance(&self) -> U128 {
-        MIN_ATTACHED_BALANCE.into()
+        self.internal_min_attached_balance().into()
+    }
+
+    /// Returns the configuration changes that were announced but are not in effect yet
+    pub fn get_pending_config(&self) -> FactoryPendingConfig {
+        FactoryPendingConfig {
+            min_attached_balance: self.min_attached_balance.pending().map(|change| PendingChange {
+                value: change.value.into(),
+                effective_at: change.effective_at,
+            }),
+            posting_fee: self.posting_fee.pending().map(|change| PendingChange {
+                value: change.value.into(),
+                effective_at: change.effective_at,
+            }),
+            treasury_account_id: self.treasury_account_id.pending(),
+            operator_fee_split: self.operator_fee_split.pending(),
+            gas_config: self.gas_config.pending(),
+            fee_discount_tiers: self.fee_discount_tiers.pending(),
+            archive_retention: self.archive_retention.pending().map(|change| PendingChange {
+                value: change.value.into(),
+                effective_at: change.effective_at,
+            }),
+            verify_tender_account_id: self.verify_tender_account_id.pending(),
+            parameter_registry_account_id: self.parameter_registry_account_id.pending(),
+            timelock_delay: self.timelock_delay.pending().map(|change| PendingChange {
+                value: change.value.into(),
+                effective_at: change.effective_at,
+            }),
+        }
+    }
+
+    /// Returns the posting fee discounts of issuers by number of completed tenders
+    pub fn get_fee_discount_tiers(&self) -> Vec<FeeDiscountTier> {
+        self.fee_discount_tiers.get()
+    }
+
+    /// Returns the number of tenders created by the given issuer that were completed
+    pub fn get_completed_tenders(&self, issuer_account_id: AccountId) -> u64 {
+        self.completed_tenders_by_issuer.get(&issuer_account_id).unwrap_or(0)
+    }
+
+    /// Returns the posting fee the given issuer pays, with the discount of its completed tenders
+    pub fn get_fee_for(&self, issuer_account_id: AccountId) -> U128 {
+        self.internal_posting_fee_for(&issuer_account_id).into()
+    }
+
+    /// Returns the time closed tenders are kept before being archived, in nanoseconds
+    pub fn get_archive_retention(&self) -> U64 {
+        self.archive_retention.get().into()
+    }
+
+    /// Returns the promo code with the given sha256 code hash, if it exists, including a used up one
+    pub fn get_promo_code(&self, code_hash: Base64VecU8) -> Option<PromoCode> {
+        self.promo_codes.get(&code_hash.0)
+    }
+
+    /// Returns the creation credit sponsors funded for the given issuer
+    pub fn get_sponsor_credit(&self, issuer_account_id: AccountId) -> U128 {
+        self.sponsor_credits
+            .get(&issuer_account_id)
+            .map(|credit| credit.amount())
+            .unwrap_or(0)
+            .into()
+    }
+
+    /// Returns the gas the factory attaches to its cross-contract calls
+    pub fn get_gas_config(&self) -> GasConfig {
+        self.gas_config.get()
+    }
+
+    /// Returns the posting fee kept from the attached deposit of `create_tender`, on top of the minimum attached balance
+    pub fn get_posting_fee(&self) -> U128 {
+        self.internal_posting_fee().into()
+    }
+
+    /// Returns the account ID of the parameter registry, if set
+    pub fn get_parameter_registry(&self) -> Option<AccountId> {
+        self.parameter_registry_account_id.get()
+    }
+
+    /// Returns the platform parameters cached from the parameter registry, if any
+    pub fn get_cached_parameters(&self) -> Option<PlatformParameters> {
+        self.internal_registry_parameters()
+    }
+
+    /// Returns the account ID of the protocol treasury receiving the posting fees, if set
+    pub fn get_treasury_account_id(&self) -> Option<AccountId> {
+        self.treasury_account_id.get()
+    }
+
+    /// Returns the split of the posting fee with the third party operating the factory, if any
+    pub fn get_operator_fee_split(&self) -> Option<OperatorFeeSplit> {
+        self.operator_fee_split.get()
+    }
+
+    /// Returns `true` if the creation of new tenders is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns the cached state of the platform-wide circuit breaker
+    pub fn is_circuit_breaker_tripped(&self) -> bool {
+        self.circuit_breaker_tripped
+    }
+
+    /// Returns the council of the factory, if set
+    pub fn get_council(&self) -> Option<Council> {
+        self.council.get()
+    }
+
+    /// Returns up to `limit` council proposals that can still be confirmed, starting at `from_index`
+    pub fn get_pending_proposals(&self, from_index: u64, limit: u64) -> Vec<ProposalView> {
+        let keys = self.council_proposals.keys_as_vector();
+        let values = self.council_proposals.values_as_vector();
+        (from_index..std::cmp::min(from_index + limit, keys.len()))
+            .map(|index| (keys.get(index).unwrap(), values.get(index).unwrap()))
+            .filter(|(_, proposal)| !proposal.is_expired())
+            .map(|(id, proposal)| ProposalView {
+                id,
+                proposer: proposal.proposer,
+                action: proposal.action,
+                confirmations: proposal.confirmations,
+                expires_at: proposal.expires_at.into(),
+            })
+            .collect()
     }
     
 
     /// Returns the total number of tenders created from this factory
-    pub fn get_number_of_tenders_created(&self) -> U64 {
+    pub fn get_number_of_tenders_created(&self) -> u64 {
         self.tender_account_ids.len()
     }
 
+    /// Returns the total number of tenders from this factory that were finalized and archived
+    pub fn get_number_of_tenders_archived(&self) -> u64 {
+        self.archived_tender_account_ids.len()
+    }
+
+    /// Returns `true` if the given tender was finalized and archived
+    pub fn is_tender_archived(&self, tender_account_id: AccountId) -> bool {
+        self.archived_tender_account_ids.contains(&tender_account_id)
+    }
+
+    /// Returns the compact record of the given archived tender, if it was archived
+    pub fn get_archived_tender(&self, tender_account_id: AccountId) -> Option<ArchivedTender> {
+        self.archived_tenders.get(&tender_account_id)
+    }
+
+    /// Returns up to `limit` archived tenders with their compact records, starting at `from_index`,
+    /// in the order they were archived
+    pub fn get_archived_tenders(&self, from_index: u64, limit: u64) -> Vec<(AccountId, ArchivedTender)> {
+        let tender_account_ids = self.archived_tender_account_ids.as_vector();
+        (from_index..std::cmp::min(from_index + limit, tender_account_ids.len()))
+            .filter_map(|index| {
+                let tender_account_id = tender_account_ids.get(index).unwrap();
+                let archived = self.archived_tenders.get(&tender_account_id)?;
+                Some((tender_account_id, archived))
+            })
+            .collect()
+    }
+
+    /// Returns the number of closed tenders waiting for the retention period before being archived
+    pub fn get_number_of_tenders_closed(&self) -> u64 {
+        self.closed_tenders.len()
+    }
+
+    /// Returns up to `limit` tenders created from this factory with their complete state, starting at `from_index`.
+    /// Lets indexers bootstrap from RPC without replaying the history of the factory.
+    /// Archived tenders are left out, only their compact record is kept, see `get_archived_tender`.
+    pub fn dump_tenders(&self, from_index: u64, limit: u64) -> Vec<TenderDump> {
+        let tender_account_ids = self.tender_account_ids.as_vector();
+        (from_index..std::cmp::min(from_index + limit, tender_account_ids.len()))
+            .filter_map(|index| {
+                let tender_account_id = tender_account_ids.get(index).unwrap();
+                let record: TenderRecord = self.tender_records.get(&tender_account_id)?.into();
+                let status = if self.closed_tenders.get(&tender_account_id).is_some() {
+                    TenderStatus::Closed
+                } else if record.created
+                    && record.parameters.opens_at.map_or(false, |opens_at| opens_at.0 > env::block_timestamp())
+                {
+                    TenderStatus::Scheduled
+                } else if record.created {
+                    TenderStatus::Active
+                } else {
+                    TenderStatus::Creating
+                };
+                Some(TenderDump {
+                    tender_account_id,
+                    status,
+                    parameters: record.parameters,
+                    creator_account_id: record.creator_account_id,
+                    posting_fee: record.posting_fee.into(),
+                    created_at: record.created_at.into(),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the sha256 hash of the award criteria the given tender was created with, if it exists.
+    /// Bidders compare it with the hash of the criteria the tender publishes.
+    pub fn get_criteria_hash(&self, tender_account_id: AccountId) -> Option<Base64VecU8> {
+        self.tender_records.get(&tender_account_id).map(|record| {
+            let record: TenderRecord = record.into();
+            record.parameters.criteria.hash().into()
+        })
+    }
+
+    /// Returns the milestone template with the given ID, if it exists
+    pub fn get_milestone_template(&self, template_id: u64) -> Option<MilestoneTemplate> {
+        self.milestone_templates.get(&template_id).map(MilestoneTemplate::from)
+    }
+
+    /// Returns up to `limit` published milestone templates with their IDs, starting at `from_index`
+    pub fn get_milestone_templates(&self, from_index: u64, limit: u64) -> Vec<(u64, MilestoneTemplate)> {
+        let keys = self.milestone_templates.keys_as_vector();
+        let values = self.milestone_templates.values_as_vector();
+        (from_index..std::cmp::min(from_index + limit, keys.len()))
+            .map(|index| (keys.get(index).unwrap(), values.get(index).unwrap().into()))
+            .collect()
+    }
+
 
-    /// Creates a new tender
+    /// Creates a new tender. The optional promo code reduces or waives the posting fee.
+    /// With `opens_at_ns`, the tender is announced in advance: it is deployed right away but accepts no bids
+    /// before the given block timestamp in nanoseconds.
     #[payable]
     pub fn create_tender(
         &mut self,
-	tender_registration_id: String,
-	owner_id: AccountId,
-	tender_public_key: Base58PublicKey,
-	tender_proposal: String,
-        product: String,
-        unitproductprice: U128,
-        quantityproduct: u64,
+        tender_registration_id: String,
+        owner_id: AccountId,
+        tender_public_key: Base58PublicKey,
+        tender_proposal: String,
+        line_items: Vec<LineItem>,
         industry: String,
-        location: String,
+        delivery_points: Vec<DeliveryPoint>,
+        criteria: EvaluationCriteria,
+        emergency: bool,
+        milestone_template_id: Option<u64>,
+        promo_code: Option<String>,
+        opens_at_ns: Option<U64>,
     ) -> Promise {
-        assert!(
-	    // To change this and add a proper fee for tender creation t	    //aking into account gas costs for storage
-	    env::attached_deposit() = MIN_ATTACHED_BALANCE,
-	    "Not enough attached deposit to issue the tender"
-	);
-
-	assert!(
-	    tender_registration_id.find('.').is_none(),
-	    "The tender registration ID can't contain `.`"
-	);
-
-	let tender_account_id = format!("{}.{}", tender_registration_id, env::current_account_id());
-        assert!(
-            env::is_valid_account_id(tender_account_id.as_bytes()),
-            "The tender account ID is invalid"
-        );
-
-	assert!(
-	    env::is_valid_account_id(owner_id.as_bytes()),
-	    "The owner account ID is invalid"
-	);
-
-	assert!(
-	    self.tender_account_ids.insert(&tender_account_id),
-	    "The tender account ID already exists"
-	);
-
-
-	Promise::new(tender_account_id.clone())
-	    .create_account()
-	    .transfer(env::attached_deposit())
-	    .deploy_contract(include_bytes!("../../tender/res/tender.wasm").to_vec())
-	    .function_call(
-	        b"new".to_vec(),
-		near_sdk::serde_json::to_vec(&TenderParameters {
-		    owner_id,
-		    tender_public_key,
-		    //---to add more tender parameters--
-		})
-		.unwrap(),
-		NO_DEPOSIT,
-		gas::TENDER_NEW,
-	    )
-	    .then(ext_self::on_tender_create(
-	        tender_account_id,
-		env::attached_deposit().into(),
-		env::predecessor_account_id(),
-		&env::current_account_id(),
-		NO_DEPOSIT,
-		gas::CALLBACk,
-	    ))
+        self.internal_create_tender(
+            CreateTenderArgs {
+                tender_registration_id,
+                owner_id,
+                tender_public_key,
+                tender_proposal,
+                line_items,
+                industry,
+                delivery_points,
+                criteria,
+                emergency,
+                milestone_template_id,
+                promo_code,
+                opens_at_ns,
+            },
+            vec![],
+        )
+    }
 
-}
+    /// Creates a new tender from Borsh-encoded arguments.
+    /// Same as `create_tender`, but skips the JSON parsing of the arguments, which adds up for large proposals.
+    #[payable]
+    pub fn create_tender_borsh(&mut self, #[serializer(borsh)] args: CreateTenderArgs) -> Promise {
+        self.internal_create_tender(args, vec![])
+    }
 
+    /// Creates a new tender with the parameters of the given tender, e.g. a recurring monthly supply order,
+    /// replaced by the given overrides. The delivery due dates usually need to be overridden.
+    /// This method can only be called by the account that created the source tender, while it is not archived.
+    #[payable]
+    pub fn repost_tender(
+        &mut self,
+        source_tender_account_id: AccountId,
+        new_registration_id: String,
+        overrides: TenderOverrides,
+    ) -> Promise {
+        let record: TenderRecord = self
+            .tender_records
+            .get(&source_tender_account_id)
+            .unwrap_or_else(|| Error::TenderRecordNotFound.panic())
+            .into();
+        require!(
+            record.creator_account_id == env::predecessor_account_id(),
+            Error::NotTenderCreator
+        );
+        let parameters = record.parameters;
+        self.internal_create_tender(
+            CreateTenderArgs {
+                tender_registration_id: new_registration_id,
+                owner_id: overrides.owner_id.unwrap_or(parameters.owner_id),
+                tender_public_key: overrides.tender_public_key.unwrap_or(parameters.tender_public_key),
+                tender_proposal: overrides.tender_proposal.unwrap_or(parameters.tender_proposal),
+                line_items: overrides.line_items.unwrap_or(parameters.line_items),
+                industry: overrides.industry.unwrap_or(parameters.industry),
+                delivery_points: overrides.delivery_points.unwrap_or(parameters.delivery_points),
+                criteria: overrides.criteria.unwrap_or(parameters.criteria),
+                emergency: overrides.emergency.unwrap_or(parameters.emergency),
+                milestone_template_id: overrides.milestone_template_id,
+                promo_code: overrides.promo_code,
+                opens_at_ns: overrides.opens_at_ns,
+            },
+            parameters.milestones,
+        )
+    }
 
 /// Callback function after a tender was created
 /// Returns the promise to verify the tender contract if the tender crea///tion was successful
-/// If not then it refunds the attached deposit and returns `false`.
+/// and pays out the posting fee to the factory operator and the treasury.
+/// If not then it refunds the attached deposit, including the posting fee, gives the use of the
+/// promo code and the sponsor credit drawn back, if any, and returns `false`.
 pub fn on_tender_create(
     &mut self,
         tender_account_id: AccountId,
         attached_deposit: U128,
+        posting_fee: U128,
         predecessor_account_id: AccountId,
+        promo_code_hash: Option<Base64VecU8>,
+        sponsor_draws: Vec<(AccountId, U128)>,
 	//---To Add More Parameters--
     ) -> PromiseOrValue<bool> {
         assert_self();
 
         let tender_created = is_promise_success();
+        let record = self.tender_records.get(&tender_account_id).map(TenderRecord::from);
+        // Events of emergency tenders are tagged, so audits can single out the fast-tracked procurements
+        let tag = match &record {
+            Some(record) if record.parameters.emergency => "[emergency] ",
+            _ => "",
+        };
 
         if tender_created {
-            env::log(
+            self.events.emit(
                 format!(
-                    "The tender @{} was successfully created. Securing...",
-                    tender_account_id
+                    "{}The tender @{} was successfully created. Securing...",
+                    tag, tender_account_id
                 )
                 .as_bytes(),
             );
-            ext_whitelist::add_tender(
+            if let Some(mut record) = record {
+                record.created = true;
+                self.tender_records.insert(&tender_account_id, &record.into());
+            }
+            if posting_fee.0 > 0 {
+                self.internal_pay_posting_fee(&tender_account_id, posting_fee.0);
+            }
+            ext_verify_tender::add_tender(
                 tender_account_id,
-                &self.verify_tender_account_id,
+                Some(env::sha256(TENDER_CODE).into()),
+                &self.verify_tender_account_id.get(),
                 NO_DEPOSIT,
-                gas::VERIFY_TENDER,
+                self.gas_config.get().verify_tender.0,
             )
             .into()
         } else {
-            self.tender_account_ids
-                .remove(&tender_account_id);
-            env::log(
-                format!(
-                    "The tender @{} creation process has failed. Returning attached deposit of {} to @{}",
-                    tender_account_id,
-                    attached_deposit.0,
-                    predecessor_account_id
-                ).as_bytes()
+            // Gives the quota reserved for the tender back to the factory
+            ext_verify_tender::release_tender(
+                tender_account_id.clone(),
+                &self.verify_tender_account_id.get(),
+                NO_DEPOSIT,
+                self.gas_config.get().verify_tender.0,
+            );
+            self.internal_rollback_tender_creation(
+                &tender_account_id,
+                attached_deposit.0,
+                predecessor_account_id,
+                promo_code_hash,
+                sponsor_draws,
             );
-            Promise::new(predecessor_account_id).transfer(attached_deposit.0);
             PromiseOrValue::Value(false)
         }
     }
-}
-		    
 
-	
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use near_sdk::{testing_env, MockedBlockchain, PromiseResult};
+    /// Callback after the Verify Tender contract reserved the verification of the new tender within the quota
+    /// and the industry scope of the factory. Deploys the tender, or rolls its creation back if the reservation
+    /// failed.
+    pub fn on_tender_reserved(
+        &mut self,
+        tender_account_id: AccountId,
+        attached_deposit: U128,
+        posting_fee: U128,
+        predecessor_account_id: AccountId,
+        promo_code_hash: Option<Base64VecU8>,
+        sponsor_draws: Vec<(AccountId, U128)>,
+    ) -> PromiseOrValue<bool> {
+        assert_self();
 
-    mod test_utils;
-    use std::convert::TryInto;
-    use test_utils::*;
+        if !is_promise_success() {
+            self.internal_rollback_tender_creation(
+                &tender_account_id,
+                attached_deposit.0,
+                predecessor_account_id,
+                promo_code_hash,
+                sponsor_draws,
+            );
+            return PromiseOrValue::Value(false);
+        }
+
+        let parameters = self
+            .tender_records
+            .get(&tender_account_id)
+            .map(|record| TenderRecord::from(record).parameters)
+            .unwrap_or_else(|| Error::TenderRecordNotFound.panic());
+        let (init_method_name, init_args) = if TENDER_BORSH_INIT {
+            (b"new_borsh".to_vec(), parameters.try_to_vec().unwrap())
+        } else {
+            (b"new".to_vec(), near_sdk::serde_json::to_vec(&parameters).unwrap())
+        };
+        let deposit = attached_deposit.0 + sponsor_draws.iter().map(|(_, amount)| amount.0).sum::<Balance>();
+
+        Promise::new(tender_account_id.clone())
+            .create_account()
+            .transfer(deposit - posting_fee.0)
+            .deploy_contract(TENDER_CODE.to_vec())
+            .function_call(init_method_name, init_args, NO_DEPOSIT, self.internal_tender_new_gas())
+            .then(ext_self::on_tender_create(
+                tender_account_id,
+                attached_deposit,
+                posting_fee,
+                predecessor_account_id,
+                promo_code_hash,
+                sponsor_draws,
+                &env::current_account_id(),
+                NO_DEPOSIT,
+                self.gas_config.get().callback.0,
+            ))
+            .into()
+    }
+
+
+    /// Refreshes the cached state of the platform-wide circuit breaker from the Verify Tender contract.
+    /// Anyone can call it, e.g. the foundation right after tripping or resetting the circuit breaker.
+    pub fn refresh_circuit_breaker(&mut self) -> Promise {
+        let gas_config = self.gas_config.get();
+        ext_verify_tender::is_circuit_breaker_tripped(
+            &self.verify_tender_account_id.get(),
+            NO_DEPOSIT,
+            gas_config.verify_tender.0,
+        )
+        .then(ext_self::on_circuit_breaker_refreshed(
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            gas_config.circuit_breaker_callback.0,
+        ))
+    }
+
+    /// Callback caching the circuit breaker state returned by the Verify Tender contract.
+    /// Returns the cached state. A failed lookup keeps the previous state.
+    pub fn on_circuit_breaker_refreshed(&mut self) -> bool {
+        assert_self();
+        if let PromiseResult::Successful(value) = env::promise_result(0) {
+            if let Ok(tripped) = near_sdk::serde_json::from_slice::<bool>(&value) {
+                if tripped != self.circuit_breaker_tripped {
+                    self.events.emit(
+                        format!(
+                            "The circuit breaker is {}",
+                            if tripped { "tripped" } else { "reset" }
+                        )
+                        .as_bytes(),
+                    );
+                }
+                self.circuit_breaker_tripped = tripped;
+            }
+        }
+        self.circuit_breaker_tripped
+    }
+
+
+    /// Refreshes the platform parameters cached from the parameter registry.
+    /// Anyone can call it, e.g. the foundation once new parameters are in effect.
+    pub fn refresh_parameters(&mut self) -> Promise {
+        let parameter_registry_account_id = self
+            .parameter_registry_account_id
+            .get()
+            .unwrap_or_else(|| Error::NoParameterRegistry.panic());
+        let gas_config = self.gas_config.get();
+        ext_parameter_registry::get_parameters(
+            &parameter_registry_account_id,
+            NO_DEPOSIT,
+            gas_config.parameter_registry.0,
+        )
+        .then(ext_self::on_parameters_refreshed(
+            parameter_registry_account_id,
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            gas_config.parameters_callback.0,
+        ))
+    }
+
+    /// Callback caching the platform parameters returned by the given parameter registry.
+    /// Returns `true` if the cache was updated. A failed lookup, or an answer from a registry
+    /// that was replaced in the meantime, keeps the previous parameters.
+    pub fn on_parameters_refreshed(&mut self, parameter_registry_account_id: AccountId) -> bool {
+        assert_self();
+        if self.parameter_registry_account_id.get().as_ref() != Some(&parameter_registry_account_id) {
+            return false;
+        }
+        if let PromiseResult::Successful(value) = env::promise_result(0) {
+            if let Ok(parameters) = near_sdk::serde_json::from_slice::<PlatformParameters>(&value) {
+                self.cached_parameters.set(&(parameter_registry_account_id, parameters));
+                self.events.emit(b"The platform parameters are refreshed");
+                return true;
+            }
+        }
+        false
+    }
+
+
+    /// Callback from a tender created by this factory, sent when the tender is
+    /// Completed/Cancelled, all claims are settled and it deletes its own account.
+    /// Moves the tender into the archive and counts a completed tender towards the fee discount of its issuer.
+    /// Returns `false` if it was already archived.
+    pub fn on_tender_finalized(&mut self, outcome: TenderOutcome) -> bool {
+        let tender_account_id = env::predecessor_account_id();
+        require!(self.tender_account_ids.contains(&tender_account_id), Error::NotFactoryTender);
+        if self.archived_tender_account_ids.contains(&tender_account_id) {
+            return false;
+        }
+        self.events.emit(
+            format!(
+                "The tender @{} was finalized and its account deleted. Archiving...",
+                tender_account_id
+            )
+            .as_bytes(),
+        );
+        // A tender that reported its closure before was counted then
+        let closed_at = match self.closed_tenders.remove(&tender_account_id) {
+            Some(closed) => closed.closed_at,
+            None => {
+                self.internal_count_completed_tender(&tender_account_id, &outcome);
+                env::block_timestamp()
+            }
+        };
+        self.internal_archive_tender(&tender_account_id, outcome, closed_at)
+    }
+
+    /// Callback from a tender created by this factory after its code was upgraded, reporting the hash of the
+    /// code it now runs. Forwards it to the verify contract, which suspends the verification of the tender
+    /// if it isn't the code the tender was verified with.
+    pub fn on_tender_upgraded(&mut self, code_hash: Base64VecU8) -> Promise {
+        let tender_account_id = env::predecessor_account_id();
+        require!(self.tender_account_ids.contains(&tender_account_id), Error::NotFactoryTender);
+        ext_verify_tender::revalidate_tender(
+            tender_account_id,
+            code_hash,
+            &self.verify_tender_account_id.get(),
+            NO_DEPOSIT,
+            self.gas_config.get().verify_tender.0,
+        )
+    }
+
+    /// Callback from a tender created by this factory, sent when the tender is Completed/Cancelled
+    /// but keeps its account. `archive_closed_tenders` archives it once the retention period passed,
+    /// deleting its account if `delete_on_archive` is set. Counts a completed tender towards the fee discount
+    /// of its issuer.
+    /// Returns `false` if the closure was already reported.
+    pub fn on_tender_closed(&mut self, outcome: TenderOutcome, delete_on_archive: bool) -> bool {
+        let tender_account_id = env::predecessor_account_id();
+        require!(self.tender_account_ids.contains(&tender_account_id), Error::NotFactoryTender);
+        if self.archived_tender_account_ids.contains(&tender_account_id)
+            || self.closed_tenders.get(&tender_account_id).is_some()
+        {
+            return false;
+        }
+        self.internal_count_completed_tender(&tender_account_id, &outcome);
+        self.closed_tenders.insert(
+            &tender_account_id,
+            &ClosedTender {
+                outcome,
+                closed_at: env::block_timestamp(),
+                delete_on_archive,
+                deleting: false,
+            },
+        );
+        self.events.emit(format!("The tender @{} was closed", tender_account_id).as_bytes());
+        true
+    }
+
+    /// Visits up to `limit` closed tenders, resuming where the previous call stopped, and archives those whose
+    /// retention period passed. The tenders that opted in delete their accounts first, sweeping their residual
+    /// balance to their issuer, and are archived by `on_tender_deleted` once the deletion succeeded.
+    /// Returns the number of tenders archived or whose deletion was requested. Anyone can call it.
+    pub fn archive_closed_tenders(&mut self, limit: u64) -> u64 {
+        let retention = self.archive_retention.get();
+        let keys = self.closed_tenders.keys_as_vector();
+        let len = keys.len();
+        let mut index = if self.archive_cursor < len { self.archive_cursor } else { 0 };
+        let mut due_tenders = vec![];
+        for _ in 0..std::cmp::min(limit, len) {
+            let tender_account_id = keys.get(index).unwrap();
+            let closed = self.closed_tenders.get(&tender_account_id).unwrap();
+            if !closed.deleting && closed.closed_at.saturating_add(retention) <= env::block_timestamp() {
+                due_tenders.push((tender_account_id, closed));
+            }
+            index = (index + 1) % len;
+        }
+        self.archive_cursor = index;
+
+        let archived = due_tenders.len() as u64;
+        for (tender_account_id, mut closed) in due_tenders {
+            let record = self.tender_records.get(&tender_account_id).map(TenderRecord::from);
+            match record {
+                Some(record) if closed.delete_on_archive => {
+                    closed.deleting = true;
+                    self.closed_tenders.insert(&tender_account_id, &closed);
+                    let gas_config = self.gas_config.get();
+                    ext_tender::delete_tender(
+                        record.creator_account_id,
+                        &tender_account_id,
+                        NO_DEPOSIT,
+                        gas_config.tender_delete.0,
+                    )
+                    .then(ext_self::on_tender_deleted(
+                        tender_account_id,
+                        &env::current_account_id(),
+                        NO_DEPOSIT,
+                        gas_config.tender_deleted_callback.0,
+                    ));
+                }
+                _ => {
+                    self.closed_tenders.remove(&tender_account_id);
+                    self.internal_archive_tender(&tender_account_id, closed.outcome, closed.closed_at);
+                }
+            }
+        }
+        archived
+    }
+
+    /// Callback after a closed tender was asked to delete its account. Archives the tender once its account
+    /// is deleted. A failed deletion leaves it closed, for a later `archive_closed_tenders` to retry.
+    /// Returns `true` if the tender was archived, `false` otherwise.
+    pub fn on_tender_deleted(&mut self, tender_account_id: AccountId) -> bool {
+        assert_self();
+        let mut closed = match self.closed_tenders.get(&tender_account_id) {
+            Some(closed) => closed,
+            None => return false,
+        };
+        if !is_promise_success() {
+            closed.deleting = false;
+            self.closed_tenders.insert(&tender_account_id, &closed);
+            self.events.emit(format!("The deletion of the tender @{} failed", tender_account_id).as_bytes());
+            return false;
+        }
+        self.closed_tenders.remove(&tender_account_id);
+        self.internal_archive_tender(&tender_account_id, closed.outcome, closed.closed_at)
+    }
+
+    /**************/
+    /* Tenderbox Foundation */
+    /**************/
+
+    /// Proposes the given account ID, e.g. a Sputnik DAO, as the new Tenderbox foundation account.
+    /// The role is only handed over once the proposed account calls `accept_foundation_role`,
+    /// which a DAO does through a function-call proposal.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn propose_foundation_account_id(&mut self, foundation_account_id: AccountId) {
+        self.assert_called_by_foundation();
+        self.foundation.propose(foundation_account_id);
+    }
+
+    /// Accepts the Tenderbox foundation role proposed to the predecessor.
+    /// This method can only be called by the account proposed with `propose_foundation_account_id`.
+    pub fn accept_foundation_role(&mut self) {
+        self.foundation.accept();
+        self.events.emit(
+            format!(
+                "The Tenderbox foundation role was handed over to @{}",
+                self.foundation.account_id()
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Publishes a reusable milestone template that issuers can reference at tender creation.
+    /// The milestone payment shares must add up to 100% (10000 basis points).
+    /// Returns the ID of the published template.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn add_milestone_template(&mut self, template: MilestoneTemplate) -> u64 {
+        self.assert_called_by_foundation();
+        require!(!template.milestones.is_empty(), Error::EmptyMilestoneTemplate);
+        require!(
+            template
+                .milestones
+                .iter()
+                .map(|milestone| milestone.payment_bps as u32)
+                .sum::<u32>() == 10_000,
+            Error::MilestoneSharesMismatch
+        );
+        let template_id = self.next_milestone_template_id;
+        self.next_milestone_template_id += 1;
+        self.milestone_templates.insert(&template_id, &template.into());
+        template_id
+    }
+
+    /// Removes the milestone template with the given ID. Tenders already created from it keep their
+    /// expanded milestones. Returns `true` if the template existed, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn remove_milestone_template(&mut self, template_id: u64) -> bool {
+        self.assert_called_by_foundation();
+        self.milestone_templates.remove(&template_id).is_some()
+    }
+
+    /// Funds creation credit for the given issuer with the attached deposit. When the issuer creates a tender,
+    /// the credit covers what the attached deposit lacks. Returns the total credit of the issuer.
+    #[payable]
+    pub fn sponsor_issuer(&mut self, issuer_id: AccountId) -> U128 {
+        require!(env::is_valid_account_id(issuer_id.as_bytes()), Error::InvalidAccountId);
+        require!(env::attached_deposit() > 0, Error::DepositNotPositive);
+        let mut credit = self.sponsor_credits.get(&issuer_id).unwrap_or_default();
+        credit.add(env::predecessor_account_id(), env::attached_deposit());
+        self.sponsor_credits.insert(&issuer_id, &credit);
+        self.events.emit(
+            format!(
+                "@{} sponsored {} of creation credit for @{}",
+                env::predecessor_account_id(),
+                env::attached_deposit(),
+                issuer_id
+            )
+            .as_bytes(),
+        );
+        credit.amount().into()
+    }
+
+    /// Sends the unused credit the predecessor funded for the given issuer back.
+    pub fn withdraw_sponsorship(&mut self, issuer_id: AccountId) -> Promise {
+        let sponsor_account_id = env::predecessor_account_id();
+        let mut credit = self
+            .sponsor_credits
+            .get(&issuer_id)
+            .unwrap_or_else(|| Error::NoSponsorCredit.panic());
+        let index = credit
+            .sponsors
+            .iter()
+            .position(|(account_id, _)| *account_id == sponsor_account_id)
+            .unwrap_or_else(|| Error::NoSponsorCredit.panic());
+        let (_, amount) = credit.sponsors.remove(index);
+        if credit.sponsors.is_empty() {
+            self.sponsor_credits.remove(&issuer_id);
+        } else {
+            self.sponsor_credits.insert(&issuer_id, &credit);
+        }
+        Promise::new(sponsor_account_id).transfer(amount)
+    }
+
+    /// Adds a promo code issuers can pass to `create_tender` to reduce or waive the posting fee,
+    /// identified by the sha256 hash of the code. A used up code is kept with no uses left, so a failed
+    /// creation can give its use back, until the foundation removes it.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn add_promo_code(&mut self, code_hash: Base64VecU8, promo_code: PromoCode) {
+        self.assert_called_by_foundation();
+        require!(
+            code_hash.0.len() == 32
+                && promo_code.remaining_uses > 0
+                && promo_code.expires_at.0 > env::block_timestamp(),
+            Error::InvalidPromoCode
+        );
+        require!(promo_code.discount_bps <= 10_000, Error::DiscountTooHigh);
+        require!(self.promo_codes.get(&code_hash.0).is_none(), Error::PromoCodeAlreadyExists);
+        self.promo_codes.insert(&code_hash.0, &promo_code);
+    }
+
+    /// Removes the promo code with the given sha256 code hash, e.g. to end a campaign early.
+    /// Returns `true` if the code existed, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn remove_promo_code(&mut self, code_hash: Base64VecU8) -> bool {
+        self.assert_called_by_foundation();
+        self.promo_codes.remove(&code_hash.0).is_some()
+    }
+
+    /// Executes the given sensitive operation directly.
+    /// This method can only be called by the Tenderbox foundation while no council is set.
+    pub fn execute_action(&mut self, action: CouncilAction) {
+        self.assert_called_by_foundation();
+        require!(self.council.is_none(), Error::CouncilConfirmationRequired);
+        self.internal_execute_action(action);
+    }
+
+
+    /***********/
+    /* Council */
+    /***********/
+
+    /// Proposes the given sensitive operation to the council, confirmed by the proposer.
+    /// The proposal is executed as soon as it has enough confirmations. Returns the proposal ID.
+    /// This method can only be called by a council member.
+    pub fn propose(&mut self, action: CouncilAction) -> u64 {
+        let council = self.assert_called_by_council();
+        if let CouncilAction::SetCouncil { council: Some(new_council) } = &action {
+            new_council.assert_valid();
+        }
+        let proposal_id = self.next_proposal_id;
+        self.next_proposal_id += 1;
+        let proposal = Proposal {
+            proposer: env::predecessor_account_id(),
+            action,
+            confirmations: vec![env::predecessor_account_id()],
+            expires_at: env::block_timestamp() + PROPOSAL_EXPIRY,
+        };
+        self.events.emit(
+            format!(
+                "The council proposal #{} was created by @{}",
+                proposal_id, proposal.proposer
+            )
+            .as_bytes(),
+        );
+        if council.threshold == 1 {
+            self.internal_execute_action(proposal.action);
+        } else {
+            self.council_proposals.insert(&proposal_id, &proposal);
+        }
+        proposal_id
+    }
+
+    /// Confirms the given council proposal. Returns `true` if the proposal was executed.
+    /// This method can only be called by a council member.
+    pub fn confirm(&mut self, proposal_id: u64) -> bool {
+        let council = self.assert_called_by_council();
+        let mut proposal = self
+            .council_proposals
+            .get(&proposal_id)
+            .unwrap_or_else(|| Error::ProposalNotFound.panic());
+        require!(!proposal.is_expired(), Error::ProposalExpired);
+        let member = env::predecessor_account_id();
+        require!(!proposal.confirmations.contains(&member), Error::ProposalAlreadyConfirmed);
+        proposal.confirmations.push(member);
+        if proposal.confirmations.len() >= council.threshold as usize {
+            self.council_proposals.remove(&proposal_id);
+            self.events.emit(format!("The council proposal #{} is executed", proposal_id).as_bytes());
+            self.internal_execute_action(proposal.action);
+            true
+        } else {
+            self.council_proposals.insert(&proposal_id, &proposal);
+            false
+        }
+    }
+
+    /// Deploys the given code on the factory account. The code has to match the code hash
+    /// approved with `CouncilAction::UpgradeFactory`.
+    pub fn upgrade(&mut self, code: Base64VecU8) -> Promise {
+        let approved_code_hash = self
+            .approved_code_hash
+            .take()
+            .unwrap_or_else(|| Error::NoApprovedFactoryCode.panic());
+        require!(env::sha256(&code.0) == approved_code_hash, Error::CodeHashMismatch);
+        self.events.emit(b"Upgrading the factory code");
+        Promise::new(env::current_account_id())
+            .deploy_contract(code.0)
+            .function_call(b"migrate".to_vec(), vec![], NO_DEPOSIT, gas::MIGRATE)
+    }
+
+
+    /************/
+    /* Internal */
+    /************/
+
+    /// Internal method to verify the predecessor was the Tenderbox Foundation account ID.
+    fn assert_called_by_foundation(&self) {
+        self.foundation.assert_called_by();
+    }
+
+    /// Internal method to verify the predecessor is a council member. Returns the council.
+    fn assert_called_by_council(&self) -> Council {
+        let council = self.council.get().unwrap_or_else(|| Error::NoCouncil.panic());
+        require!(council.is_member(&env::predecessor_account_id()), Error::NotCouncilMember);
+        council
+    }
+
+    /// Internal method to split the posting fee of the given tender between the factory operator
+    /// and the Tenderbox foundation. The foundation share goes to the treasury, or to the foundation
+    /// account directly while no treasury is set.
+    fn internal_pay_posting_fee(&self, tender_account_id: &AccountId, posting_fee: Balance) {
+        let mut foundation_share = posting_fee;
+        if let Some(split) = self.operator_fee_split.get() {
+            let (operator_share, remainder) = split.split(posting_fee);
+            foundation_share = remainder;
+            if operator_share > 0 {
+                Promise::new(split.operator_account_id).transfer(operator_share);
+            }
+        }
+        if foundation_share == 0 {
+            return;
+        }
+        match self.treasury_account_id.get() {
+            Some(treasury_account_id) => {
+                ext_treasury::deposit(
+                    FeeKind::PostingFee,
+                    Some(tender_account_id.clone()),
+                    &treasury_account_id,
+                    foundation_share,
+                    self.gas_config.get().treasury_deposit.0,
+                );
+            }
+            None => {
+                Promise::new(self.foundation.account_id().clone()).transfer(foundation_share);
+            }
+        }
+    }
+
+    /// Internal method to create a new tender with the given arguments.
+    /// Without a milestone template, the tender gets the given milestones, e.g. those of a reposted tender.
+    fn internal_create_tender(&mut self, args: CreateTenderArgs, milestones: Vec<Milestone>) -> Promise {
+        require!(!self.paused, Error::Paused);
+        require!(!self.circuit_breaker_tripped, Error::CircuitBreakerTripped);
+        let mut posting_fee = self.internal_posting_fee_for(&env::predecessor_account_id());
+        let promo_code_hash = args
+            .promo_code
+            .as_ref()
+            .map(|code| Base64VecU8::from(env::sha256(code.as_bytes())));
+        if let Some(code_hash) = &promo_code_hash {
+            let discount_bps = self.internal_use_promo_code(code_hash);
+            posting_fee -= posting_fee * Balance::from(discount_bps) / 10_000;
+        }
+        let required_deposit = self.internal_min_attached_balance() + posting_fee;
+        let sponsor_draws = if env::attached_deposit() < required_deposit {
+            self.internal_draw_sponsor_credit(
+                &env::predecessor_account_id(),
+                required_deposit - env::attached_deposit(),
+            )
+        } else {
+            vec![]
+        };
+        let tender_account_id = Self::internal_tender_account_id(&args.tender_registration_id);
+
+        require!(env::is_valid_account_id(args.owner_id.as_bytes()), Error::InvalidOwnerAccountId);
+        require!(!args.line_items.is_empty(), Error::NoLineItems);
+        require!(!args.delivery_points.is_empty(), Error::NoDeliveryPoints);
+        require!(
+            args.delivery_points
+                .iter()
+                .all(|point| point.quantities.len() == args.line_items.len()),
+            Error::DeliveryQuantityMismatch
+        );
+        for (index, line_item) in args.line_items.iter().enumerate() {
+            require!(
+                args.delivery_points
+                    .iter()
+                    .map(|point| point.quantities[index])
+                    .sum::<u64>()
+                    == line_item.quantity,
+                Error::DeliveryQuantityMismatch
+            );
+        }
+        require!(
+            args.delivery_points.iter().all(|point| point.due_at.0 > env::block_timestamp()),
+            Error::DeliveryDueInPast
+        );
+        if let Some(opens_at) = args.opens_at_ns {
+            require!(opens_at.0 > env::block_timestamp(), Error::OpeningInPast);
+            require!(
+                args.delivery_points.iter().all(|point| point.due_at.0 > opens_at.0),
+                Error::DeliveryDueBeforeOpening
+            );
+        }
+        args.criteria.assert_valid();
+
+        let milestones = match args.milestone_template_id {
+            Some(template_id) => {
+                MilestoneTemplate::from(
+                    self.milestone_templates
+                        .get(&template_id)
+                        .unwrap_or_else(|| Error::MilestoneTemplateNotFound.panic()),
+                )
+                .milestones
+            }
+            None => milestones,
+        };
+
+        require!(self.tender_account_ids.insert(&tender_account_id), Error::TenderAlreadyExists);
+
+        let parameters = TenderParameters {
+            owner_id: args.owner_id,
+            tender_public_key: args.tender_public_key,
+            tender_proposal: args.tender_proposal,
+            line_items: args.line_items,
+            industry: args.industry,
+            delivery_points: args.delivery_points,
+            criteria: args.criteria,
+            emergency: args.emergency,
+            milestones,
+            opens_at: args.opens_at_ns,
+        };
+        self.tender_records.insert(
+            &tender_account_id,
+            &TenderRecord {
+                parameters: parameters.clone(),
+                creator_account_id: env::predecessor_account_id(),
+                posting_fee,
+                created_at: env::block_timestamp(),
+                created: false,
+            }
+            .into(),
+        );
+
+        // The Verify Tender contract checks the quota and the industry scope of the factory before the deployment
+        let gas_config = self.gas_config.get();
+        ext_verify_tender::reserve_tender(
+            tender_account_id.clone(),
+            parameters.industry,
+            &self.verify_tender_account_id.get(),
+            NO_DEPOSIT,
+            gas_config.verify_tender.0,
+        )
+        .then(ext_self::on_tender_reserved(
+            tender_account_id,
+            env::attached_deposit().into(),
+            posting_fee.into(),
+            env::predecessor_account_id(),
+            promo_code_hash,
+            sponsor_draws
+                .into_iter()
+                .map(|(sponsor_account_id, amount)| (sponsor_account_id, amount.into()))
+                .collect(),
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            self.internal_tender_new_gas() + gas_config.callback.0 * 2,
+        ))
+    }
+
+    /// Internal method rolling back the creation of the given tender: the attached deposit goes back to the
+    /// issuer, the promo code use and the sponsor credit drawn are restored.
+    fn internal_rollback_tender_creation(
+        &mut self,
+        tender_account_id: &AccountId,
+        attached_deposit: Balance,
+        predecessor_account_id: AccountId,
+        promo_code_hash: Option<Base64VecU8>,
+        sponsor_draws: Vec<(AccountId, U128)>,
+    ) {
+        let tag = match self.tender_records.get(tender_account_id).map(TenderRecord::from) {
+            Some(record) if record.parameters.emergency => "[emergency] ",
+            _ => "",
+        };
+        self.tender_account_ids.remove(tender_account_id);
+        self.tender_records.remove(tender_account_id);
+        if let Some(code_hash) = promo_code_hash {
+            self.internal_restore_promo_code_use(&code_hash);
+        }
+        if !sponsor_draws.is_empty() {
+            let mut credit = self.sponsor_credits.get(&predecessor_account_id).unwrap_or_default();
+            credit.restore(
+                sponsor_draws
+                    .into_iter()
+                    .map(|(sponsor_account_id, amount)| (sponsor_account_id, amount.0))
+                    .collect(),
+            );
+            self.sponsor_credits.insert(&predecessor_account_id, &credit);
+        }
+        self.events.emit(
+            format!(
+                "{}The tender @{} creation process has failed. Returning attached deposit of {} to @{}",
+                tag, tender_account_id, attached_deposit, predecessor_account_id
+            )
+            .as_bytes(),
+        );
+        Promise::new(predecessor_account_id).transfer(attached_deposit);
+    }
+
+    /// Internal method returning the account ID of the tender with the given registration ID,
+    /// a sub-account of the factory.
+    fn internal_tender_account_id(tender_registration_id: &str) -> AccountId {
+        require!(tender_registration_id.find('.').is_none(), Error::InvalidTenderRegistrationId);
+        let tender_account_id = format!("{}.{}", tender_registration_id, env::current_account_id());
+        require!(
+            env::is_valid_account_id(tender_account_id.as_bytes()),
+            Error::InvalidTenderAccountId
+        );
+        tender_account_id
+    }
+
+    /// Internal method returning the minimum attached balance, from the parameter registry if set.
+    fn internal_min_attached_balance(&self) -> Balance {
+        match self.internal_registry_parameters() {
+            Some(parameters) => parameters.min_attached_balance.0,
+            None => self.min_attached_balance.get(),
+        }
+    }
+
+    /// Internal method returning the posting fee, from the parameter registry if set.
+    fn internal_posting_fee(&self) -> Balance {
+        match self.internal_registry_parameters() {
+            Some(parameters) => parameters.posting_fee.0,
+            None => self.posting_fee.get(),
+        }
+    }
+
+    /// Internal method returning the posting fee of the given issuer, with the highest discount of the tiers
+    /// its completed tenders reach.
+    fn internal_posting_fee_for(&self, issuer_account_id: &AccountId) -> Balance {
+        let completed_tenders = self.get_completed_tenders(issuer_account_id.clone());
+        let discount_bps = self
+            .fee_discount_tiers
+            .get()
+            .iter()
+            .filter(|tier| completed_tenders >= tier.min_completed_tenders)
+            .map(|tier| tier.discount_bps)
+            .max()
+            .unwrap_or(0);
+        let posting_fee = self.internal_posting_fee();
+        posting_fee - posting_fee * Balance::from(discount_bps) / 10_000
+    }
+
+    /// Internal method to move the given tender into the archive, replacing its creation record with a
+    /// compact one.
+    /// Returns `false` if it was already archived.
+    fn internal_archive_tender(
+        &mut self,
+        tender_account_id: &AccountId,
+        outcome: TenderOutcome,
+        closed_at: u64,
+    ) -> bool {
+        if !self.archived_tender_account_ids.insert(tender_account_id) {
+            return false;
+        }
+        if let Some(record) = self.tender_records.remove(tender_account_id) {
+            let record = TenderRecord::from(record);
+            self.archived_tenders.insert(
+                tender_account_id,
+                &ArchivedTender {
+                    owner_id: record.parameters.owner_id,
+                    creator_account_id: record.creator_account_id,
+                    outcome,
+                    created_at: record.created_at.into(),
+                    closed_at: closed_at.into(),
+                },
+            );
+        }
+        true
+    }
+
+    /// Internal method counting the given tender towards the fee discount of its issuer if it was completed.
+    fn internal_count_completed_tender(&mut self, tender_account_id: &AccountId, outcome: &TenderOutcome) {
+        if let TenderOutcome::Completed { .. } = outcome {
+            if let Some(record) = self.tender_records.get(tender_account_id) {
+                let creator_account_id = TenderRecord::from(record).creator_account_id;
+                let completed_tenders = self.get_completed_tenders(creator_account_id.clone()) + 1;
+                self.completed_tenders_by_issuer.insert(&creator_account_id, &completed_tenders);
+            }
+        }
+    }
+
+    /// Internal method to draw the given amount from the sponsor credit of the given issuer.
+    /// Returns the amount drawn from each sponsor.
+    fn internal_draw_sponsor_credit(
+        &mut self,
+        issuer_account_id: &AccountId,
+        amount: Balance,
+    ) -> Vec<(AccountId, Balance)> {
+        let mut credit = self.sponsor_credits.get(issuer_account_id).unwrap_or_default();
+        require!(credit.amount() >= amount, Error::NotEnoughDepositForTender);
+        let draws = credit.draw(amount);
+        if credit.sponsors.is_empty() {
+            self.sponsor_credits.remove(issuer_account_id);
+        } else {
+            self.sponsor_credits.insert(issuer_account_id, &credit);
+        }
+        draws
+    }
+
+    /// Internal method to use the promo code with the given code hash once. Returns its discount in basis points.
+    fn internal_use_promo_code(&mut self, code_hash: &Base64VecU8) -> u16 {
+        let mut promo_code = self
+            .promo_codes
+            .get(&code_hash.0)
+            .unwrap_or_else(|| Error::PromoCodeNotFound.panic());
+        require!(promo_code.remaining_uses > 0, Error::PromoCodeNotFound);
+        require!(env::block_timestamp() <= promo_code.expires_at.0, Error::PromoCodeExpired);
+        promo_code.remaining_uses -= 1;
+        self.promo_codes.insert(&code_hash.0, &promo_code);
+        promo_code.discount_bps
+    }
+
+    /// Internal method to give back the use of a promo code after the tender creation failed.
+    /// A code removed by the foundation in the meantime stays removed.
+    fn internal_restore_promo_code_use(&mut self, code_hash: &Base64VecU8) {
+        if let Some(mut promo_code) = self.promo_codes.get(&code_hash.0) {
+            promo_code.remaining_uses += 1;
+            self.promo_codes.insert(&code_hash.0, &promo_code);
+        }
+    }
+
+    /// Internal method returning the gas attached to the initialization of a new tender,
+    /// from the parameter registry if set.
+    fn internal_tender_new_gas(&self) -> Gas {
+        match self.internal_registry_parameters() {
+            Some(parameters) => parameters.tender_new_gas.0,
+            None => self.gas_config.get().tender_new.0,
+        }
+    }
+
+    /// Internal method returning the cached parameters if they come from the parameter registry in effect.
+    fn internal_registry_parameters(&self) -> Option<PlatformParameters> {
+        match (self.parameter_registry_account_id.get(), self.cached_parameters.get()) {
+            (Some(registry), Some((source, parameters))) if registry == source => Some(parameters),
+            _ => None,
+        }
+    }
+
+    /// Internal method to execute a confirmed sensitive operation.
+    fn internal_execute_action(&mut self, action: CouncilAction) {
+        match action {
+            CouncilAction::UpgradeFactory { code_hash } => {
+                self.approved_code_hash = Some(code_hash.0);
+            }
+            CouncilAction::SetMinAttachedBalance { min_attached_balance } => {
+                let effective_at = self
+                    .min_attached_balance
+                    .schedule(min_attached_balance.0, self.timelock_delay.get());
+                self.events.emit(
+                    format!(
+                        "The minimum attached balance changes to {} at {}",
+                        min_attached_balance.0, effective_at
+                    )
+                    .as_bytes(),
+                );
+            }
+            CouncilAction::SetPostingFee { posting_fee } => {
+                let effective_at = self
+                    .posting_fee
+                    .schedule(posting_fee.0, self.timelock_delay.get());
+                self.events.emit(
+                    format!(
+                        "The posting fee changes to {} at {}",
+                        posting_fee.0, effective_at
+                    )
+                    .as_bytes(),
+                );
+            }
+            CouncilAction::SetTreasuryAccount { treasury_account_id } => {
+                require!(
+                    env::is_valid_account_id(treasury_account_id.as_bytes()),
+                    Error::InvalidTreasuryAccountId
+                );
+                let effective_at = self
+                    .treasury_account_id
+                    .schedule(Some(treasury_account_id.clone()), self.timelock_delay.get());
+                self.events.emit(
+                    format!(
+                        "The treasury account changes to @{} at {}",
+                        treasury_account_id, effective_at
+                    )
+                    .as_bytes(),
+                );
+            }
+            CouncilAction::SetOperatorFeeSplit { operator_fee_split } => {
+                if let Some(split) = operator_fee_split.as_ref() {
+                    require!(
+                        env::is_valid_account_id(split.operator_account_id.as_bytes()),
+                        Error::InvalidOperatorAccountId
+                    );
+                    require!(split.operator_fee_bps <= 10_000, Error::OperatorFeeTooHigh);
+                }
+                let effective_at = self
+                    .operator_fee_split
+                    .schedule(operator_fee_split, self.timelock_delay.get());
+                self.events.emit(format!("The operator fee split changes at {}", effective_at).as_bytes());
+            }
+            CouncilAction::SetGasConfig { gas_config } => {
+                gas_config.assert_valid();
+                let effective_at = self.gas_config.schedule(gas_config, self.timelock_delay.get());
+                self.events.emit(format!("The gas configuration changes at {}", effective_at).as_bytes());
+            }
+            CouncilAction::SetFeeDiscountTiers { fee_discount_tiers } => {
+                require!(
+                    fee_discount_tiers.iter().all(|tier| tier.discount_bps <= 10_000),
+                    Error::DiscountTooHigh
+                );
+                let effective_at = self
+                    .fee_discount_tiers
+                    .schedule(fee_discount_tiers, self.timelock_delay.get());
+                self.events.emit(format!("The fee discount tiers change at {}", effective_at).as_bytes());
+            }
+            CouncilAction::SetArchiveRetention { archive_retention } => {
+                let effective_at = self
+                    .archive_retention
+                    .schedule(archive_retention.0, self.timelock_delay.get());
+                self.events.emit(format!("The archive retention changes at {}", effective_at).as_bytes());
+            }
+            CouncilAction::SetVerifyTenderAccount { verify_tender_account_id } => {
+                require!(
+                    env::is_valid_account_id(verify_tender_account_id.as_bytes()),
+                    Error::InvalidVerifyTenderAccountId
+                );
+                let effective_at = self
+                    .verify_tender_account_id
+                    .schedule(verify_tender_account_id.clone(), self.timelock_delay.get());
+                self.events.emit(
+                    format!(
+                        "The verify tender account changes to @{} at {}",
+                        verify_tender_account_id, effective_at
+                    )
+                    .as_bytes(),
+                );
+            }
+            CouncilAction::SetParameterRegistry { parameter_registry_account_id } => {
+                if let Some(account_id) = parameter_registry_account_id.as_ref() {
+                    require!(
+                        env::is_valid_account_id(account_id.as_bytes()),
+                        Error::InvalidParameterRegistryAccountId
+                    );
+                }
+                let effective_at = self
+                    .parameter_registry_account_id
+                    .schedule(parameter_registry_account_id, self.timelock_delay.get());
+                self.events.emit(format!("The parameter registry changes at {}", effective_at).as_bytes());
+            }
+            CouncilAction::SetTimelockDelay { timelock_delay } => {
+                let effective_at = self
+                    .timelock_delay
+                    .schedule(timelock_delay.0, self.timelock_delay.get());
+                self.events.emit(
+                    format!(
+                        "The timelock delay changes to {} at {}",
+                        timelock_delay.0, effective_at
+                    )
+                    .as_bytes(),
+                );
+            }
+            CouncilAction::CancelPendingConfig => {
+                self.min_attached_balance.cancel();
+                self.posting_fee.cancel();
+                self.treasury_account_id.cancel();
+                self.operator_fee_split.cancel();
+                self.gas_config.cancel();
+                self.fee_discount_tiers.cancel();
+                self.archive_retention.cancel();
+                self.verify_tender_account_id.cancel();
+                self.parameter_registry_account_id.cancel();
+                self.timelock_delay.cancel();
+                self.events.emit(b"The pending configuration changes are cancelled");
+            }
+            CouncilAction::Pause => {
+                self.paused = true;
+                self.events.emit(b"The creation of new tenders is paused");
+            }
+            CouncilAction::Unpause => {
+                self.paused = false;
+                self.events.emit(b"The creation of new tenders is resumed");
+            }
+            CouncilAction::SetCouncil { council } => {
+                match council {
+                    Some(council) => {
+                        council.assert_valid();
+                        self.council.set(&council);
+                    }
+                    None => {
+                        self.council.remove();
+                    }
+                }
+                self.council_proposals.clear();
+            }
+        }
+    }
+}
+		    
+
+	
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::{testing_env, MockedBlockchain, PromiseResult};
+
+    mod test_utils;
+    use proptest::prelude::*;
+    use std::convert::TryInto;
+    use test_utils::*;
+
+    /// Due date of the deliveries of the sample tender, in nanoseconds. 30 days.
+    const DELIVERY_DUE_AT: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+    /// Award criteria of the sample tender.
+    fn lowest_price_criteria() -> EvaluationCriteria {
+        EvaluationCriteria {
+            mode: AwardMode::LowestPrice,
+            weights: vec![],
+            mandatory_requirements: vec!["ISO 9001 certification".to_string()],
+        }
+    }
+
+    /// Calls `create_tender` with the given registration ID and the parameters of a sample tender.
+    fn create_tender(contract: &mut TenderFactory, tender_registration_id: String) -> Promise {
+        create_tender_with_promo_code(contract, tender_registration_id, None)
+    }
+
+    /// Outcome of a sample tender completed by the supplier.
+    fn completed_outcome() -> TenderOutcome {
+        TenderOutcome::Completed {
+            winner_id: account_supplier(),
+            final_amount: ntoy(450).into(),
+        }
+    }
+
+    /// Same as `create_tender`, passing the given promo code.
+    fn create_tender_with_promo_code(
+        contract: &mut TenderFactory,
+        tender_registration_id: String,
+        promo_code: Option<String>,
+    ) -> Promise {
+        contract.create_tender(
+            tender_registration_id,
+            account_tender_owner(),
+            "KuTCtARNzxZQ3YvXDeLjx83FDqxv2SdQTSbiq876zR7"
+                .try_into()
+                .unwrap(),
+            "Supply of 500 office chairs and 20 desks".to_string(),
+            vec![
+                LineItem {
+                    product: "Office chair".to_string(),
+                    unit_budget: ntoy(1).into(),
+                    quantity: 500,
+                },
+                LineItem {
+                    product: "Office desk".to_string(),
+                    unit_budget: ntoy(5).into(),
+                    quantity: 20,
+                },
+            ],
+            "Furniture".to_string(),
+            vec![
+                DeliveryPoint {
+                    location: "Nairobi".to_string(),
+                    quantities: vec![300, 12],
+                    due_at: DELIVERY_DUE_AT.into(),
+                },
+                DeliveryPoint {
+                    location: "Mombasa".to_string(),
+                    quantities: vec![200, 8],
+                    due_at: DELIVERY_DUE_AT.into(),
+                },
+            ],
+            lowest_price_criteria(),
+            false,
+            None,
+            promo_code,
+            None,
+        )
+    }
 
     #[test]
     fn test_create_tender_success() {
@@ -258,7 +2124,7 @@ mod tests {
             .finish();
         testing_env!(context.clone());
 
-        let mut contract = TenderFactory::new(account_verify_tender());
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
 
         context.is_view = true;
         testing_env!(context.clone());
@@ -269,18 +2135,19 @@ mod tests {
         context.predecessor_account_id = account_tokens_owner();
         context.attached_deposit = ntoy(31);
         testing_env!(context.clone());
-        contract.create_tender(
-            tender_id(),
-            account_tender_owner(),
-            "KuTCtARNzxZQ3YvXDeLjx83FDqxv2SdQTSbiq876zR7"
-                .try_into()
-                .unwrap(),
-        );
+        create_tender(&mut contract, tender_registration_id());
 
         context.predecessor_account_id = account_factory();
         context.attached_deposit = ntoy(0);
         testing_env_with_promise_results(context.clone(), PromiseResult::Successful(vec![]));
-        contract.on_tender_create(account_pool(), ntoy(31).into(), account_tokens_owner());
+        contract.on_tender_create(
+            account_tender(),
+            ntoy(31).into(),
+            0.into(),
+            account_tokens_owner(),
+            None,
+            vec![],
+        );
 
         context.is_view = true;
         testing_env!(context.clone());
@@ -288,25 +2155,65 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Not enough attached deposit to complete tender creation")]
-    fn test_create_tender_not_enough_deposit() {
+    fn test_create_tender_borsh() {
         let mut context = VMContextBuilder::new()
             .current_account_id(account_factory())
             .predecessor_account_id(account_tenderbox())
             .finish();
         testing_env!(context.clone());
 
-        let mut contract = TenderFactory::new(account_verify_tender());
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+
+        context.predecessor_account_id = account_tokens_owner();
+        context.attached_deposit = ntoy(31);
+        testing_env!(context.clone());
+        contract.create_tender_borsh(CreateTenderArgs {
+            tender_registration_id: tender_registration_id(),
+            owner_id: account_tender_owner(),
+            tender_public_key: "KuTCtARNzxZQ3YvXDeLjx83FDqxv2SdQTSbiq876zR7"
+                .try_into()
+                .unwrap(),
+            tender_proposal: "Supply of 500 office chairs".to_string(),
+            line_items: vec![LineItem {
+                product: "Office chair".to_string(),
+                unit_budget: ntoy(1).into(),
+                quantity: 500,
+            }],
+            industry: "Furniture".to_string(),
+            delivery_points: vec![DeliveryPoint {
+                location: "Nairobi".to_string(),
+                quantities: vec![500],
+                due_at: DELIVERY_DUE_AT.into(),
+            }],
+            criteria: lowest_price_criteria(),
+            emergency: true,
+            milestone_template_id: None,
+            promo_code: None,
+            opens_at_ns: None,
+        });
 
-        // Checking the pool is still whitelisted
         context.is_view = true;
         testing_env!(context.clone());
-        assert_eq!(contract.get_min_attached_balance().0, MIN_ATTACHED_BALANCE);
-        assert_eq!(contract.get_number_of_tender_created(), 0);
+        assert_eq!(contract.get_number_of_tenders_created(), 1);
+        let dump = contract.dump_tenders(0, 10);
+        assert_eq!(dump[0].tender_account_id, account_tender());
+        assert_eq!(dump[0].parameters.line_items[0].product, "Office chair");
+        assert!(dump[0].parameters.emergency);
+    }
+
+    #[test]
+    #[should_panic(expected = "E420: The delivery point quantities must add up to the line item quantities")]
+    fn test_create_tender_delivery_quantity_mismatch() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
 
-        context.is_view = false;
         context.predecessor_account_id = account_tokens_owner();
-        context.attached_deposit = ntoy(20);
+        context.attached_deposit = ntoy(31);
         testing_env!(context.clone());
         contract.create_tender(
             tender_registration_id(),
@@ -314,45 +2221,190 @@ mod tests {
             "KuTCtARNzxZQ3YvXDeLjx83FDqxv2SdQTSbiq876zR7"
                 .try_into()
                 .unwrap(),
-	);
+            "Supply of 500 office chairs".to_string(),
+            vec![LineItem {
+                product: "Office chair".to_string(),
+                unit_budget: ntoy(1).into(),
+                quantity: 500,
+            }],
+            "Furniture".to_string(),
+            vec![DeliveryPoint {
+                location: "Nairobi".to_string(),
+                quantities: vec![400],
+                due_at: DELIVERY_DUE_AT.into(),
+            }],
+            lowest_price_criteria(),
+            false,
+            None,
+            None,
+            None,
+        );
     }
 
     #[test]
-    fn test_create_tender_rollback() {
+    fn test_criteria_hash() {
         let mut context = VMContextBuilder::new()
             .current_account_id(account_factory())
             .predecessor_account_id(account_tenderbox())
             .finish();
         testing_env!(context.clone());
 
-        let mut contract = TenderFactory::new(account_verify());
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        assert!(contract.get_criteria_hash(account_tender()).is_none());
+
+        context.predecessor_account_id = account_tokens_owner();
+        context.attached_deposit = ntoy(31);
+        testing_env!(context.clone());
+        create_tender(&mut contract, tender_registration_id());
 
         context.is_view = true;
         testing_env!(context.clone());
-        assert_eq!(contract.get_min_attached_balance().0, MIN_ATTACHED_BALANCE);
-        assert_eq!(contract.get_number_of_tender_created(), 0);
+        assert_eq!(
+            Vec::<u8>::from(contract.get_criteria_hash(account_tender()).unwrap()),
+            lowest_price_criteria().hash()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "E423: The criterion weights must add up to 10000 basis points")]
+    fn test_create_tender_criterion_weights_mismatch() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
 
-        context.is_view = false;
         context.predecessor_account_id = account_tokens_owner();
         context.attached_deposit = ntoy(31);
         testing_env!(context.clone());
-        contract.create_tender(
-            tender_registration_id(),
-            account_tender_owner(),
-            "KuTCtARNzxZQ3YvXDeLjx83FDqxv2SdQTSbiq876zR7"
+        contract.create_tender_borsh(CreateTenderArgs {
+            tender_registration_id: tender_registration_id(),
+            owner_id: account_tender_owner(),
+            tender_public_key: "KuTCtARNzxZQ3YvXDeLjx83FDqxv2SdQTSbiq876zR7"
                 .try_into()
                 .unwrap(),
-            
-        );
+            tender_proposal: "Supply of 500 office chairs".to_string(),
+            line_items: vec![LineItem {
+                product: "Office chair".to_string(),
+                unit_budget: ntoy(1).into(),
+                quantity: 500,
+            }],
+            industry: "Furniture".to_string(),
+            delivery_points: vec![DeliveryPoint {
+                location: "Nairobi".to_string(),
+                quantities: vec![500],
+                due_at: DELIVERY_DUE_AT.into(),
+            }],
+            criteria: EvaluationCriteria {
+                mode: AwardMode::WeightedScore,
+                weights: vec![
+                    CriterionWeight { name: "Price".to_string(), weight_bps: 6_000 },
+                    CriterionWeight { name: "Delivery time".to_string(), weight_bps: 3_000 },
+                ],
+                mandatory_requirements: vec![],
+            },
+            emergency: false,
+            milestone_template_id: None,
+            promo_code: None,
+            opens_at_ns: None,
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "E601: Not enough attached deposit to issue the tender")]
+    fn test_create_tender_not_enough_deposit() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+
+        // Checking the pool is still whitelisted
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_min_attached_balance().0, MIN_ATTACHED_BALANCE);
+        assert_eq!(contract.get_number_of_tenders_created(), 0);
+
+        context.is_view = false;
+        context.predecessor_account_id = account_tokens_owner();
+        context.attached_deposit = ntoy(20);
+        testing_env!(context.clone());
+        create_tender(&mut contract, tender_registration_id());
+    }
+
+    #[test]
+    fn test_create_tender_rollback() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_min_attached_balance().0, MIN_ATTACHED_BALANCE);
+        assert_eq!(contract.get_number_of_tenders_created(), 0);
+
+        context.is_view = false;
+        context.predecessor_account_id = account_tokens_owner();
+        context.attached_deposit = ntoy(31);
+        testing_env!(context.clone());
+        create_tender(&mut contract, tender_registration_id());
 
         context.predecessor_account_id = account_factory();
         context.attached_deposit = ntoy(0);
         context.account_balance += ntoy(31);
         testing_env_with_promise_results(context.clone(), PromiseResult::Failed);
         let res = contract.on_tender_create(
-            account_pool(),
+            account_tender(),
+            ntoy(31).into(),
+            0.into(),
+            account_tokens_owner(),
+            None,
+            vec![],
+        );
+        match res {
+            PromiseOrValue::Promise(_) => panic!("Unexpected result, should return Value(false)"),
+            PromiseOrValue::Value(value) => assert!(!value),
+        };
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_number_of_tenders_created(), 0);
+    }
+
+    #[test]
+    fn test_create_tender_reservation_failed() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+
+        context.predecessor_account_id = account_tokens_owner();
+        context.attached_deposit = ntoy(31);
+        testing_env!(context.clone());
+        create_tender(&mut contract, tender_registration_id());
+
+        // The Verify Tender contract rejects the tender, e.g. out of the scope of the factory
+        context.predecessor_account_id = account_factory();
+        context.attached_deposit = ntoy(0);
+        testing_env_with_promise_results(context.clone(), PromiseResult::Failed);
+        let res = contract.on_tender_reserved(
+            account_tender(),
             ntoy(31).into(),
+            0.into(),
             account_tokens_owner(),
+            None,
+            vec![],
         );
         match res {
             PromiseOrValue::Promise(_) => panic!("Unexpected result, should return Value(false)"),
@@ -363,4 +2415,1008 @@ mod tests {
         testing_env!(context.clone());
         assert_eq!(contract.get_number_of_tenders_created(), 0);
     }
+
+    #[test]
+    fn test_tender_finalized() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+
+        context.is_view = false;
+        context.predecessor_account_id = account_tokens_owner();
+        context.attached_deposit = ntoy(31);
+        testing_env!(context.clone());
+        create_tender(&mut contract, tender_registration_id());
+
+        // The tender deletes its account and reports back to the factory
+        context.predecessor_account_id = account_tender();
+        context.attached_deposit = ntoy(0);
+        testing_env!(context.clone());
+        assert!(contract.on_tender_finalized(completed_outcome()));
+        assert!(!contract.on_tender_finalized(completed_outcome()));
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.is_tender_archived(account_tender()));
+        assert_eq!(contract.get_number_of_tenders_archived(), 1);
+        assert_eq!(contract.get_number_of_tenders_created(), 1);
+        assert_eq!(contract.get_completed_tenders(account_tokens_owner()), 1);
+
+        let archived = contract.get_archived_tenders(0, 10);
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].0, account_tender());
+        assert_eq!(archived[0].1.owner_id, account_tender_owner());
+        assert_eq!(archived[0].1.creator_account_id, account_tokens_owner());
+        assert_eq!(archived[0].1.outcome, completed_outcome());
+        assert_eq!(archived[0].1.closed_at.0, context.block_timestamp);
+    }
+
+    #[test]
+    fn test_repost_tender() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tokens_owner())
+            .finish();
+        context.attached_deposit = ntoy(31);
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        create_tender(&mut contract, tender_registration_id());
+        contract.repost_tender(
+            account_tender(),
+            "tender-2".to_string(),
+            TenderOverrides {
+                tender_proposal: Some("Supply of 600 office chairs and 20 desks".to_string()),
+                ..TenderOverrides::default()
+            },
+        );
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        let dump = contract.dump_tenders(0, 10);
+        assert_eq!(dump.len(), 2);
+        assert_eq!(dump[1].tender_account_id, format!("tender-2.{}", account_factory()));
+        assert_eq!(dump[1].parameters.tender_proposal, "Supply of 600 office chairs and 20 desks");
+        assert_eq!(dump[1].parameters.line_items.len(), dump[0].parameters.line_items.len());
+        assert_eq!(dump[1].parameters.owner_id, account_tender_owner());
+    }
+
+    #[test]
+    #[should_panic(expected = "E110: Can only be called by the account that created the tender")]
+    fn test_repost_tender_by_other_account() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tokens_owner())
+            .finish();
+        context.attached_deposit = ntoy(31);
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        create_tender(&mut contract, tender_registration_id());
+
+        context.predecessor_account_id = account_supplier();
+        testing_env!(context.clone());
+        contract.repost_tender(account_tender(), "tender-2".to_string(), TenderOverrides::default());
+    }
+
+    #[test]
+    fn test_scheduled_tender() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tokens_owner())
+            .finish();
+        context.attached_deposit = ntoy(31);
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        create_tender(&mut contract, tender_registration_id());
+        let opens_at = context.block_timestamp + DELIVERY_DUE_AT / 2;
+        contract.repost_tender(
+            account_tender(),
+            "tender-2".to_string(),
+            TenderOverrides {
+                opens_at_ns: Some(opens_at.into()),
+                ..TenderOverrides::default()
+            },
+        );
+
+        context.predecessor_account_id = account_factory();
+        context.attached_deposit = 0;
+        testing_env_with_promise_results(context.clone(), PromiseResult::Successful(vec![]));
+        contract.on_tender_create(
+            format!("tender-2.{}", account_factory()),
+            ntoy(31).into(),
+            0.into(),
+            account_tokens_owner(),
+            None,
+            vec![],
+        );
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        let dump = contract.dump_tenders(0, 10);
+        assert_eq!(dump[1].status, TenderStatus::Scheduled);
+        assert_eq!(dump[1].parameters.opens_at, Some(opens_at.into()));
+
+        context.block_timestamp = opens_at;
+        testing_env!(context.clone());
+        assert_eq!(contract.dump_tenders(0, 10)[1].status, TenderStatus::Active);
+    }
+
+    #[test]
+    #[should_panic(expected = "E432: The deliveries have to be due after the tender opens")]
+    fn test_scheduled_tender_delivery_due_before_opening() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tokens_owner())
+            .finish();
+        context.attached_deposit = ntoy(31);
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        create_tender(&mut contract, tender_registration_id());
+        contract.repost_tender(
+            account_tender(),
+            "tender-2".to_string(),
+            TenderOverrides {
+                opens_at_ns: Some(DELIVERY_DUE_AT.into()),
+                ..TenderOverrides::default()
+            },
+        );
+    }
+
+    #[test]
+    fn test_archive_closed_tenders() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tokens_owner())
+            .finish();
+        context.attached_deposit = ntoy(31);
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        create_tender(&mut contract, tender_registration_id());
+
+        // The tender closes and opts in to the deletion of its account
+        context.predecessor_account_id = account_tender();
+        context.attached_deposit = 0;
+        testing_env!(context.clone());
+        assert!(contract.on_tender_closed(completed_outcome(), true));
+        assert!(!contract.on_tender_closed(completed_outcome(), true));
+
+        // The completion counts towards the fee discount right away, and only once
+        assert_eq!(contract.get_completed_tenders(account_tokens_owner()), 1);
+
+        // Nothing to archive during the retention period
+        context.predecessor_account_id = account_supplier();
+        testing_env!(context.clone());
+        assert_eq!(contract.archive_closed_tenders(10), 0);
+        assert_eq!(contract.dump_tenders(0, 10)[0].status, TenderStatus::Closed);
+
+        // The tender is archived once its account is deleted, a failed deletion is retried
+        context.block_timestamp += DEFAULT_ARCHIVE_RETENTION;
+        testing_env!(context.clone());
+        assert_eq!(contract.archive_closed_tenders(10), 1);
+        assert_eq!(contract.archive_closed_tenders(10), 0);
+
+        context.predecessor_account_id = account_factory();
+        testing_env_with_promise_results(context.clone(), PromiseResult::Failed);
+        assert!(!contract.on_tender_deleted(account_tender()));
+        assert!(!contract.is_tender_archived(account_tender()));
+
+        context.predecessor_account_id = account_supplier();
+        testing_env!(context.clone());
+        assert_eq!(contract.archive_closed_tenders(10), 1);
+
+        context.predecessor_account_id = account_factory();
+        testing_env_with_promise_results(context.clone(), PromiseResult::Successful(vec![]));
+        assert!(contract.on_tender_deleted(account_tender()));
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.is_tender_archived(account_tender()));
+        assert_eq!(contract.get_number_of_tenders_closed(), 0);
+        assert!(contract.dump_tenders(0, 10).is_empty());
+        let archived = contract.get_archived_tender(account_tender()).unwrap();
+        assert_eq!(archived.owner_id, account_tender_owner());
+        assert_eq!(archived.outcome, completed_outcome());
+        assert_eq!(contract.get_completed_tenders(account_tokens_owner()), 1);
+    }
+
+    #[test]
+    fn test_fee_discount_after_completed_tenders() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        contract.execute_action(CouncilAction::SetPostingFee {
+            posting_fee: ntoy(1).into(),
+        });
+        contract.execute_action(CouncilAction::SetFeeDiscountTiers {
+            fee_discount_tiers: vec![
+                FeeDiscountTier {
+                    min_completed_tenders: 1,
+                    discount_bps: 2_500,
+                },
+                FeeDiscountTier {
+                    min_completed_tenders: 2,
+                    discount_bps: 5_000,
+                },
+            ],
+        });
+
+        context.block_timestamp += DEFAULT_TIMELOCK_DELAY;
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_fee_for(account_tokens_owner()).0, ntoy(1));
+
+        context.is_view = false;
+        context.predecessor_account_id = account_tokens_owner();
+        context.attached_deposit = MIN_ATTACHED_BALANCE + ntoy(1);
+        testing_env!(context.clone());
+        create_tender(&mut contract, tender_registration_id());
+
+        context.predecessor_account_id = account_tender();
+        context.attached_deposit = ntoy(0);
+        testing_env!(context.clone());
+        contract.on_tender_finalized(completed_outcome());
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_completed_tenders(account_tokens_owner()), 1);
+        assert_eq!(contract.get_fee_for(account_tokens_owner()).0, ntoy(1) * 3 / 4);
+        assert_eq!(contract.get_fee_for(account_tender_owner()).0, ntoy(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "E429: The discount can't exceed 10000 basis points")]
+    fn test_fee_discount_too_high() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context);
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        contract.execute_action(CouncilAction::SetFeeDiscountTiers {
+            fee_discount_tiers: vec![FeeDiscountTier {
+                min_completed_tenders: 5,
+                discount_bps: 10_001,
+            }],
+        });
+    }
+
+    #[test]
+    fn test_create_tender_with_promo_code() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        contract.execute_action(CouncilAction::SetPostingFee {
+            posting_fee: ntoy(1).into(),
+        });
+        let code_hash: Base64VecU8 = env::sha256(b"LAUNCH50").into();
+        contract.add_promo_code(
+            code_hash.clone(),
+            PromoCode {
+                discount_bps: 5_000,
+                remaining_uses: 1,
+                expires_at: (context.block_timestamp + DEFAULT_TIMELOCK_DELAY * 2).into(),
+            },
+        );
+
+        // Half of the posting fee is enough with the promo code
+        context.block_timestamp += DEFAULT_TIMELOCK_DELAY;
+        context.predecessor_account_id = account_tokens_owner();
+        context.attached_deposit = MIN_ATTACHED_BALANCE + ntoy(1) / 2;
+        testing_env!(context.clone());
+        create_tender_with_promo_code(&mut contract, tender_registration_id(), Some("LAUNCH50".to_string()));
+        assert_eq!(contract.get_promo_code(code_hash.clone()).unwrap().remaining_uses, 0);
+
+        // The failed creation gives the use back, even though the code was used up
+        context.predecessor_account_id = account_factory();
+        context.attached_deposit = ntoy(0);
+        testing_env_with_promise_results(context.clone(), PromiseResult::Failed);
+        contract.on_tender_create(
+            account_tender(),
+            (MIN_ATTACHED_BALANCE + ntoy(1) / 2).into(),
+            (ntoy(1) / 2).into(),
+            account_tokens_owner(),
+            Some(code_hash.clone()),
+            vec![],
+        );
+        assert_eq!(contract.get_promo_code(code_hash).unwrap().remaining_uses, 1);
+    }
+
+    #[test]
+    fn test_create_tender_with_sponsor_credit() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_sponsor())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+
+        context.attached_deposit = ntoy(50);
+        testing_env!(context.clone());
+        assert_eq!(contract.sponsor_issuer(account_tokens_owner()).0, ntoy(50));
+
+        // The credit covers the whole deposit of the issuer
+        context.predecessor_account_id = account_tokens_owner();
+        context.attached_deposit = 0;
+        testing_env!(context.clone());
+        create_tender(&mut contract, tender_registration_id());
+        assert_eq!(contract.get_sponsor_credit(account_tokens_owner()).0, ntoy(50) - MIN_ATTACHED_BALANCE);
+
+        // The failed creation gives the credit back
+        context.predecessor_account_id = account_factory();
+        testing_env_with_promise_results(context.clone(), PromiseResult::Failed);
+        contract.on_tender_create(
+            account_tender(),
+            0.into(),
+            0.into(),
+            account_tokens_owner(),
+            None,
+            vec![(account_sponsor(), MIN_ATTACHED_BALANCE.into())],
+        );
+        assert_eq!(contract.get_sponsor_credit(account_tokens_owner()).0, ntoy(50));
+
+        // The sponsor takes the unused credit back
+        context.predecessor_account_id = account_sponsor();
+        testing_env!(context.clone());
+        contract.withdraw_sponsorship(account_tokens_owner());
+        assert_eq!(contract.get_sponsor_credit(account_tokens_owner()).0, 0);
+    }
+
+    #[test]
+    fn test_sponsor_credit_order() {
+        let mut credit = SponsorCredit::default();
+        credit.add(account_sponsor(), ntoy(1));
+        credit.add(account_tenderbox(), ntoy(2));
+        credit.add(account_tender_owner(), ntoy(3));
+
+        // Drawing uses the first sponsor up and part of the second one
+        let draws = credit.draw(ntoy(2));
+        assert_eq!(credit.sponsors, vec![(account_tenderbox(), ntoy(1)), (account_tender_owner(), ntoy(3))]);
+
+        // A rollback puts them back where they were
+        credit.restore(draws);
+        assert_eq!(
+            credit.sponsors,
+            vec![(account_sponsor(), ntoy(1)), (account_tenderbox(), ntoy(2)), (account_tender_owner(), ntoy(3))]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "E601: Not enough attached deposit to issue the tender")]
+    fn test_create_tender_with_too_little_sponsor_credit() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_sponsor())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+
+        context.attached_deposit = ntoy(10);
+        testing_env!(context.clone());
+        contract.sponsor_issuer(account_tokens_owner());
+
+        context.predecessor_account_id = account_tokens_owner();
+        context.attached_deposit = ntoy(10);
+        testing_env!(context.clone());
+        create_tender(&mut contract, tender_registration_id());
+    }
+
+    #[test]
+    #[should_panic(expected = "E543: The promo code expired")]
+    fn test_create_tender_with_expired_promo_code() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        contract.add_promo_code(
+            env::sha256(b"LAUNCH50").into(),
+            PromoCode {
+                discount_bps: 5_000,
+                remaining_uses: 2,
+                expires_at: (context.block_timestamp + 1).into(),
+            },
+        );
+
+        context.block_timestamp += 2;
+        context.predecessor_account_id = account_tokens_owner();
+        context.attached_deposit = ntoy(31);
+        testing_env!(context.clone());
+        create_tender_with_promo_code(&mut contract, tender_registration_id(), Some("LAUNCH50".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "E105: Can only be called by a tender created by this factory")]
+    fn test_tender_finalized_by_unknown_account() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+
+        context.predecessor_account_id = account_tokens_owner();
+        testing_env!(context.clone());
+        contract.on_tender_finalized(TenderOutcome::Cancelled);
+    }
+
+    #[test]
+    #[should_panic(expected = "E105: Can only be called by a tender created by this factory")]
+    fn test_tender_upgraded_by_unknown_account() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+
+        context.predecessor_account_id = account_tokens_owner();
+        testing_env!(context.clone());
+        contract.on_tender_upgraded(env::sha256(b"other code").into());
+    }
+
+    #[test]
+    fn test_milestone_templates() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+
+        // Publishing a 30/40/30 goods delivery template by the foundation
+        let template_id = contract.add_milestone_template(MilestoneTemplate {
+            name: "30/40/30 goods delivery".to_string(),
+            milestones: vec![
+                Milestone { description: "Order confirmed".to_string(), payment_bps: 3_000 },
+                Milestone { description: "Goods shipped".to_string(), payment_bps: 4_000 },
+                Milestone { description: "Goods received".to_string(), payment_bps: 3_000 },
+            ],
+        });
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_milestone_templates(0, 10).len(), 1);
+        assert_eq!(
+            contract.get_milestone_template(template_id).unwrap().milestones.len(),
+            3
+        );
+
+        // Removing the template
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.remove_milestone_template(template_id));
+        assert!(!contract.remove_milestone_template(template_id));
+        assert!(contract.get_milestone_template(template_id).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "E406: The milestone payment shares must add up to 10000 basis points")]
+    fn test_milestone_template_incomplete_shares() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        contract.add_milestone_template(MilestoneTemplate {
+            name: "Half upfront".to_string(),
+            milestones: vec![Milestone { description: "Order confirmed".to_string(), payment_bps: 5_000 }],
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "E101: Can only be called by the Tenderbox Foundation")]
+    fn test_milestone_template_not_by_foundation() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+
+        context.predecessor_account_id = account_tender_owner();
+        testing_env!(context.clone());
+        contract.remove_milestone_template(0);
+    }
+
+    #[test]
+    fn test_migrate() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        env::state_write(&contract);
+
+        context.predecessor_account_id = account_factory();
+        testing_env!(context.clone());
+        let contract = TenderFactory::migrate();
+        assert_eq!(contract.get_foundation_account_id(), account_tenderbox());
+    }
+
+    #[test]
+    fn test_council_pause() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+
+        // Setting a 2 of 3 council by the foundation
+        contract.execute_action(CouncilAction::SetCouncil {
+            council: Some(Council {
+                members: vec![account_council_member(0), account_council_member(1), account_council_member(2)],
+                threshold: 2,
+            }),
+        });
+
+        // Proposing to pause
+        context.predecessor_account_id = account_council_member(0);
+        testing_env!(context.clone());
+        let proposal_id = contract.propose(CouncilAction::Pause);
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_pending_proposals(0, 10).len(), 1);
+        assert!(!contract.is_paused());
+
+        // The second confirmation executes the proposal
+        context.is_view = false;
+        context.predecessor_account_id = account_council_member(2);
+        testing_env!(context.clone());
+        assert!(contract.confirm(proposal_id));
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.is_paused());
+        assert!(contract.get_pending_proposals(0, 10).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "E507: The proposal has expired")]
+    fn test_council_proposal_expired() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        contract.execute_action(CouncilAction::SetCouncil {
+            council: Some(Council {
+                members: vec![account_council_member(0), account_council_member(1)],
+                threshold: 2,
+            }),
+        });
+
+        context.predecessor_account_id = account_council_member(0);
+        testing_env!(context.clone());
+        let proposal_id = contract.propose(CouncilAction::SetMinAttachedBalance {
+            min_attached_balance: ntoy(40).into(),
+        });
+
+        context.predecessor_account_id = account_council_member(1);
+        context.block_timestamp += PROPOSAL_EXPIRY;
+        testing_env!(context.clone());
+        contract.confirm(proposal_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "E108: The action has to be confirmed by the council")]
+    fn test_foundation_action_with_council() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        contract.execute_action(CouncilAction::SetCouncil {
+            council: Some(Council {
+                members: vec![account_council_member(0)],
+                threshold: 1,
+            }),
+        });
+        contract.execute_action(CouncilAction::Pause);
+    }
+
+    #[test]
+    fn test_timelocked_min_attached_balance() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+
+        // Announcing the new minimum attached balance
+        contract.execute_action(CouncilAction::SetMinAttachedBalance {
+            min_attached_balance: ntoy(40).into(),
+        });
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_min_attached_balance().0, MIN_ATTACHED_BALANCE);
+        let pending = contract.get_pending_config().min_attached_balance.unwrap();
+        assert_eq!(pending.value.0, ntoy(40));
+        assert_eq!(pending.effective_at.0, context.block_timestamp + DEFAULT_TIMELOCK_DELAY);
+
+        // The change takes effect after the delay
+        context.block_timestamp += DEFAULT_TIMELOCK_DELAY;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_min_attached_balance().0, ntoy(40));
+        assert!(contract.get_pending_config().min_attached_balance.is_none());
+    }
+
+    #[test]
+    fn test_cancel_pending_config() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        contract.execute_action(CouncilAction::SetVerifyTenderAccount {
+            verify_tender_account_id: account_verified(),
+        });
+        contract.execute_action(CouncilAction::CancelPendingConfig);
+
+        context.block_timestamp += DEFAULT_TIMELOCK_DELAY;
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.get_pending_config().verify_tender_account_id.is_none());
+    }
+
+    #[test]
+    fn test_create_tender_with_posting_fee() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        contract.execute_action(CouncilAction::SetTreasuryAccount {
+            treasury_account_id: account_treasury(),
+        });
+        contract.execute_action(CouncilAction::SetPostingFee {
+            posting_fee: ntoy(1).into(),
+        });
+
+        context.block_timestamp += DEFAULT_TIMELOCK_DELAY;
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_posting_fee().0, ntoy(1));
+        assert_eq!(contract.get_treasury_account_id(), Some(account_treasury()));
+
+        context.is_view = false;
+        context.predecessor_account_id = account_tokens_owner();
+        context.attached_deposit = MIN_ATTACHED_BALANCE + ntoy(1);
+        testing_env!(context.clone());
+        create_tender(&mut contract, tender_registration_id());
+
+        context.predecessor_account_id = account_factory();
+        context.attached_deposit = ntoy(0);
+        testing_env_with_promise_results(context.clone(), PromiseResult::Successful(vec![]));
+        contract.on_tender_create(
+            account_tender(),
+            (MIN_ATTACHED_BALANCE + ntoy(1)).into(),
+            ntoy(1).into(),
+            account_tokens_owner(),
+            None,
+            vec![],
+        );
+    }
+
+    #[test]
+    fn test_gas_config() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        assert_eq!(contract.get_gas_config(), GasConfig::default());
+
+        // Raising the gas of the tender initialization
+        let gas_config = GasConfig {
+            tender_new: (gas::TENDER_NEW * 2).into(),
+            ..GasConfig::default()
+        };
+        contract.execute_action(CouncilAction::SetGasConfig { gas_config: gas_config.clone() });
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_gas_config(), GasConfig::default());
+        assert_eq!(contract.get_pending_config().gas_config.unwrap().value, gas_config);
+
+        context.block_timestamp += DEFAULT_TIMELOCK_DELAY;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_gas_config(), gas_config);
+    }
+
+    #[test]
+    #[should_panic(expected = "E401: The gas allowances must be positive")]
+    fn test_gas_config_zero_allowance() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context);
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        contract.execute_action(CouncilAction::SetGasConfig {
+            gas_config: GasConfig {
+                callback: 0.into(),
+                ..GasConfig::default()
+            },
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "E601: Not enough attached deposit to issue the tender")]
+    fn test_create_tender_without_posting_fee() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        contract.execute_action(CouncilAction::SetPostingFee {
+            posting_fee: ntoy(1).into(),
+        });
+
+        context.block_timestamp += DEFAULT_TIMELOCK_DELAY;
+        context.predecessor_account_id = account_tokens_owner();
+        context.attached_deposit = MIN_ATTACHED_BALANCE;
+        testing_env!(context.clone());
+        create_tender(&mut contract, tender_registration_id());
+    }
+
+    #[test]
+    fn test_operator_fee_split() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        contract.execute_action(CouncilAction::SetOperatorFeeSplit {
+            operator_fee_split: Some(OperatorFeeSplit {
+                operator_account_id: account_operator(),
+                operator_fee_bps: 7_000,
+            }),
+        });
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.get_operator_fee_split().is_none());
+        assert!(contract.get_pending_config().operator_fee_split.is_some());
+
+        context.block_timestamp += DEFAULT_TIMELOCK_DELAY;
+        testing_env!(context.clone());
+        let split = contract.get_operator_fee_split().unwrap();
+        assert_eq!(split.operator_account_id, account_operator());
+        assert_eq!(split.operator_fee_bps, 7_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "E404: The operator fee share can't exceed 10000 basis points")]
+    fn test_operator_fee_split_above_total() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        contract.execute_action(CouncilAction::SetOperatorFeeSplit {
+            operator_fee_split: Some(OperatorFeeSplit {
+                operator_account_id: account_operator(),
+                operator_fee_bps: 10_001,
+            }),
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "E502: The Tenderbox platform is paused by the circuit breaker")]
+    fn test_create_tender_circuit_breaker_tripped() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+
+        // Caching the tripped circuit breaker
+        contract.refresh_circuit_breaker();
+        context.predecessor_account_id = account_factory();
+        testing_env_with_promise_results(context.clone(), PromiseResult::Successful(b"true".to_vec()));
+        assert!(contract.on_circuit_breaker_refreshed());
+
+        context.predecessor_account_id = account_tokens_owner();
+        context.attached_deposit = ntoy(31);
+        testing_env!(context.clone());
+        create_tender(&mut contract, tender_registration_id());
+    }
+
+    #[test]
+    fn test_refresh_parameters() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        contract.execute_action(CouncilAction::SetParameterRegistry {
+            parameter_registry_account_id: Some(account_parameter_registry()),
+        });
+
+        context.block_timestamp += DEFAULT_TIMELOCK_DELAY;
+        testing_env!(context.clone());
+        contract.refresh_parameters();
+
+        // Caching the parameters returned by the registry
+        context.predecessor_account_id = account_factory();
+        testing_env_with_promise_results(
+            context.clone(),
+            PromiseResult::Successful(
+                format!(
+                    r#"{{"min_attached_balance":"{}","posting_fee":"{}","award_fee_bps":50,"tender_new_gas":"50000000000000","dispute_window":"604800000000000","emergency_submission_window":"86400000000000","emergency_challenge_window":"86400000000000"}}"#,
+                    ntoy(20),
+                    ntoy(2)
+                )
+                .into_bytes(),
+            ),
+        );
+        assert!(contract.on_parameters_refreshed(account_parameter_registry()));
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_min_attached_balance().0, ntoy(20));
+        assert_eq!(contract.get_posting_fee().0, ntoy(2));
+    }
+
+    #[test]
+    fn test_dump_tenders() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_factory())
+            .predecessor_account_id(account_tokens_owner())
+            .finish();
+        context.attached_deposit = ntoy(31);
+        testing_env!(context.clone());
+
+        let mut contract = TenderFactory::new(account_verify_tender(), account_tenderbox());
+        create_tender(&mut contract, tender_registration_id());
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        let dump = contract.dump_tenders(0, 10);
+        assert_eq!(dump.len(), 1);
+        assert_eq!(dump[0].tender_account_id, account_tender());
+        assert_eq!(dump[0].status, TenderStatus::Creating);
+        assert_eq!(dump[0].creator_account_id, account_tokens_owner());
+
+        // The tender account was created
+        context.is_view = false;
+        context.predecessor_account_id = account_factory();
+        context.attached_deposit = 0;
+        testing_env_with_promise_results(context.clone(), PromiseResult::Successful(vec![]));
+        contract.on_tender_create(
+            account_tender(),
+            ntoy(31).into(),
+            0.into(),
+            account_tokens_owner(),
+            None,
+            vec![],
+        );
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.dump_tenders(0, 10)[0].status, TenderStatus::Active);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_operator_fee_split_conserves_posting_fee(
+            posting_fee in 0..=ntoy(1_000_000),
+            operator_fee_bps in 0u16..=10_000,
+        ) {
+            let split = OperatorFeeSplit {
+                operator_account_id: account_operator(),
+                operator_fee_bps,
+            };
+            let (operator_share, foundation_share) = split.split(posting_fee);
+            prop_assert_eq!(operator_share + foundation_share, posting_fee);
+            prop_assert!(operator_share <= posting_fee * Balance::from(operator_fee_bps) / 10_000);
+            if operator_fee_bps == 10_000 {
+                prop_assert_eq!(foundation_share, 0);
+            }
+        }
+
+        #[test]
+        fn prop_tender_registration_id(
+            tender_registration_id in "([a-z0-9]{1,6}[-_]){0,4}[a-z0-9]{1,6}"
+                .prop_filter("account IDs have at least 2 characters", |id| id.len() >= 2),
+        ) {
+            let context = VMContextBuilder::new()
+                .current_account_id(account_factory())
+                .finish();
+            testing_env!(context);
+            prop_assert_eq!(
+                TenderFactory::internal_tender_account_id(&tender_registration_id),
+                format!("{}.{}", tender_registration_id, account_factory())
+            );
+        }
+
+        #[test]
+        fn prop_tender_registration_id_with_adjacent_separators(
+            prefix in "[a-z0-9]{1,10}",
+            separators in "[-_]{2}",
+            suffix in "[a-z0-9]{1,10}",
+        ) {
+            let context = VMContextBuilder::new()
+                .current_account_id(account_factory())
+                .finish();
+            testing_env!(context);
+            let result = std::panic::catch_unwind(|| {
+                TenderFactory::internal_tender_account_id(&format!("{}{}{}", prefix, separators, suffix))
+            });
+            let message = result
+                .unwrap_err()
+                .downcast::<String>()
+                .map(|message| *message)
+                .unwrap_or_default();
+            prop_assert!(message.starts_with("E305: "), "unexpected panic: {}", message);
+        }
+
+        #[test]
+        fn prop_tender_registration_id_with_dot(prefix in "[a-z0-9]{1,10}", suffix in "[a-z0-9]{1,10}") {
+            let context = VMContextBuilder::new()
+                .current_account_id(account_factory())
+                .finish();
+            testing_env!(context);
+            let result = std::panic::catch_unwind(|| {
+                TenderFactory::internal_tender_account_id(&format!("{}.{}", prefix, suffix))
+            });
+            prop_assert!(result.is_err());
+        }
+    }
 }
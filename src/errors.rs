@@ -0,0 +1,418 @@
+#![allow(dead_code)]
+
+use near_sdk::env;
+use std::fmt;
+
+// Errors of the Tenderbox contracts. Every failed check panics with `E<code>: <message>`, where the
+// code is stable across releases, so integrators can match failures on the code instead of the
+// message. Codes are grouped by hundreds: 1xx access control, 2xx initialization, 3xx invalid
+// account IDs, 4xx invalid arguments, 5xx contract state and 6xx balances. Never reuse a code.
+
+/// Panics with the given `Error` unless the condition holds.
+macro_rules! require {
+    ($condition:expr, $error:expr) => {
+        if !$condition {
+            $error.panic()
+        }
+    };
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Error {
+    NotFoundation,
+    NotProposedFoundation,
+    NotSelf,
+    NotCouncilMember,
+    NotFactoryTender,
+    NotFoundationOrVerifier,
+    NotUpgradeCoordinator,
+    CouncilConfirmationRequired,
+    NotArbitrator,
+    NotTenderCreator,
+    NotTenderFactory,
+
+    NotInitialized,
+    AlreadyInitialized,
+    UnknownStateLayout,
+
+    InvalidAccountId,
+    InvalidFoundationAccountId,
+    InvalidVerifyTenderAccountId,
+    InvalidOwnerAccountId,
+    InvalidTenderAccountId,
+    InvalidTenderRegistrationId,
+    InvalidTreasuryAccountId,
+    InvalidOperatorAccountId,
+    InvalidParameterRegistryAccountId,
+    InvalidCouncilMemberAccountId,
+    InvalidBeneficiaryAccountId,
+
+    GasAllowanceNotPositive,
+    TenderGasNotPositive,
+    AwardFeeTooHigh,
+    OperatorFeeTooHigh,
+    EmptyMilestoneTemplate,
+    MilestoneSharesMismatch,
+    DuplicateCouncilMembers,
+    InvalidCouncilThreshold,
+    InvalidCodeHash,
+    InvalidCompanyNameHash,
+    UnknownCategoryCode(u32),
+    BlacklistingNotJustified,
+    BlacklistingInPast,
+    EmptyReason,
+    EmptyVersion,
+    InvalidFeeDepositMessage,
+    UpgradeBatchSize(usize),
+    DepositNotPositive,
+    NoDeliveryPoints,
+    DeliveryQuantityMismatch,
+    DeliveryDueInPast,
+    NoLineItems,
+    CriterionWeightsMismatch,
+    UnexpectedCriterionWeights,
+    InvalidSuspensionPolicy,
+    InvalidContentHash,
+    EmptyUrl,
+    ImportBatchSize(usize),
+    DiscountTooHigh,
+    InvalidPromoCode,
+    OpeningInPast,
+    DeliveryDueBeforeOpening,
+
+    Paused,
+    CircuitBreakerTripped,
+    TenderAlreadyExists,
+    MilestoneTemplateNotFound,
+    NoParameterRegistry,
+    ProposalNotFound,
+    ProposalExpired,
+    ProposalAlreadyConfirmed,
+    NoCouncil,
+    NoApprovedFactoryCode,
+    CodeHashMismatch,
+    CertificatesAlreadyMinted,
+    CertificatesNonTransferable,
+    SupplierNotRegistered,
+    NoBondToWithdraw,
+    BondUnbonding,
+    EndorserNotRegistered,
+    SelfEndorsement,
+    CategoryNotOffered(u32),
+    EndorsementLimitReached,
+    TotalValueOverflow,
+    SupplierOutOfScope,
+    BondTooSmall,
+    NoBond,
+    TokensNotAccepted,
+    UnknownToken,
+    VersionNotApproved,
+    ContractNotManaged,
+    VersionKindMismatch,
+    TenderConsentRequired,
+    PromiseResultExpected,
+    ArbitratorNotFound,
+    ArbitratorStillApproved,
+    ArbitratorHasOpenCases,
+    CaseAlreadyOpen,
+    CaseNotFound,
+    NotVerified,
+    AlreadyVerified,
+    NoReviewBounty,
+    FactoryQuotaReached,
+    PromoCodeAlreadyExists,
+    PromoCodeNotFound,
+    PromoCodeExpired,
+    NoSponsorCredit,
+    TenderRecordNotFound,
+    TenderSuspended,
+    NoVerificationRequest,
+    OutOfFactoryScope,
+    NoTenderReservation,
+
+    NotEnoughDepositForTender,
+    NotEnoughDepositForStorage,
+    NotEnoughDepositForBond,
+    NotEnoughTreasuryBalance,
+}
+
+impl Error {
+    /// Returns the stable numeric code of the error.
+    pub fn code(&self) -> u16 {
+        match self {
+            Error::NotFoundation => 101,
+            Error::NotProposedFoundation => 102,
+            Error::NotSelf => 103,
+            Error::NotCouncilMember => 104,
+            Error::NotFactoryTender => 105,
+            Error::NotFoundationOrVerifier => 106,
+            Error::NotUpgradeCoordinator => 107,
+            Error::CouncilConfirmationRequired => 108,
+            Error::NotArbitrator => 109,
+            Error::NotTenderCreator => 110,
+            Error::NotTenderFactory => 111,
+
+            Error::NotInitialized => 201,
+            Error::AlreadyInitialized => 202,
+            Error::UnknownStateLayout => 203,
+
+            Error::InvalidAccountId => 301,
+            Error::InvalidFoundationAccountId => 302,
+            Error::InvalidVerifyTenderAccountId => 303,
+            Error::InvalidOwnerAccountId => 304,
+            Error::InvalidTenderAccountId => 305,
+            Error::InvalidTenderRegistrationId => 306,
+            Error::InvalidTreasuryAccountId => 307,
+            Error::InvalidOperatorAccountId => 308,
+            Error::InvalidParameterRegistryAccountId => 309,
+            Error::InvalidCouncilMemberAccountId => 310,
+            Error::InvalidBeneficiaryAccountId => 311,
+
+            Error::GasAllowanceNotPositive => 401,
+            Error::TenderGasNotPositive => 402,
+            Error::AwardFeeTooHigh => 403,
+            Error::OperatorFeeTooHigh => 404,
+            Error::EmptyMilestoneTemplate => 405,
+            Error::MilestoneSharesMismatch => 406,
+            Error::DuplicateCouncilMembers => 407,
+            Error::InvalidCouncilThreshold => 408,
+            Error::InvalidCodeHash => 409,
+            Error::InvalidCompanyNameHash => 410,
+            Error::UnknownCategoryCode(_) => 411,
+            Error::BlacklistingNotJustified => 412,
+            Error::BlacklistingInPast => 413,
+            Error::EmptyReason => 414,
+            Error::EmptyVersion => 415,
+            Error::InvalidFeeDepositMessage => 416,
+            Error::UpgradeBatchSize(_) => 417,
+            Error::DepositNotPositive => 418,
+            Error::NoDeliveryPoints => 419,
+            Error::DeliveryQuantityMismatch => 420,
+            Error::DeliveryDueInPast => 421,
+            Error::NoLineItems => 422,
+            Error::CriterionWeightsMismatch => 423,
+            Error::UnexpectedCriterionWeights => 424,
+            Error::InvalidSuspensionPolicy => 425,
+            Error::InvalidContentHash => 426,
+            Error::EmptyUrl => 427,
+            Error::ImportBatchSize(_) => 428,
+            Error::DiscountTooHigh => 429,
+            Error::InvalidPromoCode => 430,
+            Error::OpeningInPast => 431,
+            Error::DeliveryDueBeforeOpening => 432,
+
+            Error::Paused => 501,
+            Error::CircuitBreakerTripped => 502,
+            Error::TenderAlreadyExists => 503,
+            Error::MilestoneTemplateNotFound => 504,
+            Error::NoParameterRegistry => 505,
+            Error::ProposalNotFound => 506,
+            Error::ProposalExpired => 507,
+            Error::ProposalAlreadyConfirmed => 508,
+            Error::NoCouncil => 509,
+            Error::NoApprovedFactoryCode => 510,
+            Error::CodeHashMismatch => 511,
+            Error::CertificatesAlreadyMinted => 512,
+            Error::CertificatesNonTransferable => 513,
+            Error::SupplierNotRegistered => 514,
+            Error::NoBondToWithdraw => 515,
+            Error::BondUnbonding => 516,
+            Error::EndorserNotRegistered => 517,
+            Error::SelfEndorsement => 518,
+            Error::CategoryNotOffered(_) => 519,
+            Error::EndorsementLimitReached => 520,
+            Error::TotalValueOverflow => 521,
+            Error::SupplierOutOfScope => 522,
+            Error::BondTooSmall => 523,
+            Error::NoBond => 524,
+            Error::TokensNotAccepted => 525,
+            Error::UnknownToken => 526,
+            Error::VersionNotApproved => 527,
+            Error::ContractNotManaged => 528,
+            Error::VersionKindMismatch => 529,
+            Error::TenderConsentRequired => 530,
+            Error::PromiseResultExpected => 531,
+            Error::ArbitratorNotFound => 532,
+            Error::ArbitratorStillApproved => 533,
+            Error::ArbitratorHasOpenCases => 534,
+            Error::CaseAlreadyOpen => 535,
+            Error::CaseNotFound => 536,
+            Error::NotVerified => 537,
+            Error::AlreadyVerified => 538,
+            Error::NoReviewBounty => 539,
+            Error::FactoryQuotaReached => 540,
+            Error::PromoCodeAlreadyExists => 541,
+            Error::PromoCodeNotFound => 542,
+            Error::PromoCodeExpired => 543,
+            Error::NoSponsorCredit => 544,
+            Error::TenderRecordNotFound => 545,
+            Error::TenderSuspended => 546,
+            Error::NoVerificationRequest => 547,
+            Error::OutOfFactoryScope => 548,
+            Error::NoTenderReservation => 549,
+
+            Error::NotEnoughDepositForTender => 601,
+            Error::NotEnoughDepositForStorage => 602,
+            Error::NotEnoughDepositForBond => 603,
+            Error::NotEnoughTreasuryBalance => 604,
+        }
+    }
+
+    /// Aborts the execution with the error.
+    pub fn panic(&self) -> ! {
+        env::panic(self.to_string().as_bytes())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "E{}: ", self.code())?;
+        match self {
+            Error::NotFoundation => write!(f, "Can only be called by the Tenderbox Foundation"),
+            Error::NotProposedFoundation => {
+                write!(f, "Can only be called by the proposed Tenderbox Foundation account")
+            }
+            Error::NotSelf => write!(f, "Can only be called by the contract itself"),
+            Error::NotCouncilMember => write!(f, "Can only be called by a council member"),
+            Error::NotFactoryTender => write!(f, "Can only be called by a tender created by this factory"),
+            Error::NotFoundationOrVerifier => {
+                write!(f, "Can only be called by the Tenderbox Foundation or an appointed verifier")
+            }
+            Error::NotUpgradeCoordinator => write!(f, "Can only be called by the upgrade coordinator"),
+            Error::CouncilConfirmationRequired => write!(f, "The action has to be confirmed by the council"),
+            Error::NotArbitrator => write!(f, "Can only be called by an approved arbitrator"),
+            Error::NotTenderCreator => write!(f, "Can only be called by the account that created the tender"),
+            Error::NotTenderFactory => {
+                write!(f, "Can only be called by the factory that created the tender or the Tenderbox foundation")
+            }
+
+            Error::NotInitialized => write!(f, "The contract should be initialized before usage"),
+            Error::AlreadyInitialized => write!(f, "The contract is already initialized"),
+            Error::UnknownStateLayout => write!(f, "The stored state doesn't match any known layout of the contract"),
+
+            Error::InvalidAccountId => write!(f, "The given account ID is invalid"),
+            Error::InvalidFoundationAccountId => write!(f, "The Tenderbox account ID is invalid"),
+            Error::InvalidVerifyTenderAccountId => write!(f, "The verify tender account ID is invalid"),
+            Error::InvalidOwnerAccountId => write!(f, "The owner account ID is invalid"),
+            Error::InvalidTenderAccountId => write!(f, "The tender account ID is invalid"),
+            Error::InvalidTenderRegistrationId => write!(f, "The tender registration ID can't contain `.`"),
+            Error::InvalidTreasuryAccountId => write!(f, "The treasury account ID is invalid"),
+            Error::InvalidOperatorAccountId => write!(f, "The operator account ID is invalid"),
+            Error::InvalidParameterRegistryAccountId => {
+                write!(f, "The parameter registry account ID is invalid")
+            }
+            Error::InvalidCouncilMemberAccountId => write!(f, "The council member account ID is invalid"),
+            Error::InvalidBeneficiaryAccountId => write!(f, "The beneficiary account ID is invalid"),
+
+            Error::GasAllowanceNotPositive => write!(f, "The gas allowances must be positive"),
+            Error::TenderGasNotPositive => write!(f, "The tender gas allowance must be positive"),
+            Error::AwardFeeTooHigh => write!(f, "The award fee can't be above 10000 basis points"),
+            Error::OperatorFeeTooHigh => write!(f, "The operator fee share can't exceed 10000 basis points"),
+            Error::EmptyMilestoneTemplate => write!(f, "The milestone template has no milestones"),
+            Error::MilestoneSharesMismatch => {
+                write!(f, "The milestone payment shares must add up to 10000 basis points")
+            }
+            Error::DuplicateCouncilMembers => write!(f, "The council members must be unique"),
+            Error::InvalidCouncilThreshold => {
+                write!(f, "The council threshold must be between 1 and the number of members")
+            }
+            Error::InvalidCodeHash => write!(f, "The code hash must be a sha256 hash"),
+            Error::InvalidCompanyNameHash => write!(f, "The company name hash must be a sha256 hash"),
+            Error::UnknownCategoryCode(code) => write!(f, "Unknown category code {}", code),
+            Error::BlacklistingNotJustified => write!(f, "The blacklisting has to be justified"),
+            Error::BlacklistingInPast => write!(f, "The blacklisting has to end in the future"),
+            Error::EmptyReason => write!(f, "The reason must not be empty"),
+            Error::EmptyVersion => write!(f, "The version must not be empty"),
+            Error::InvalidFeeDepositMessage => write!(f, "The message is not a valid fee deposit"),
+            Error::UpgradeBatchSize(max) => write!(f, "Can upgrade between 1 and {} contracts at once", max),
+            Error::DepositNotPositive => write!(f, "The deposit has to be positive"),
+            Error::NoDeliveryPoints => write!(f, "The tender has no delivery points"),
+            Error::DeliveryQuantityMismatch => {
+                write!(f, "The delivery point quantities must add up to the line item quantities")
+            }
+            Error::DeliveryDueInPast => write!(f, "The deliveries have to be due in the future"),
+            Error::NoLineItems => write!(f, "The tender has no line items"),
+            Error::CriterionWeightsMismatch => {
+                write!(f, "The criterion weights must add up to 10000 basis points")
+            }
+            Error::UnexpectedCriterionWeights => {
+                write!(f, "Only tenders awarded by weighted score can weigh criteria")
+            }
+            Error::InvalidSuspensionPolicy => {
+                write!(f, "The suspension policy needs a positive number of disputes and period")
+            }
+            Error::InvalidContentHash => write!(f, "The content hash must be a sha256 hash"),
+            Error::EmptyUrl => write!(f, "The URL must not be empty"),
+            Error::ImportBatchSize(max) => write!(f, "Can import between 1 and {} accounts at once", max),
+            Error::DiscountTooHigh => write!(f, "The discount can't exceed 10000 basis points"),
+            Error::InvalidPromoCode => {
+                write!(f, "The promo code needs a sha256 hash, at least one use and an expiry in the future")
+            }
+            Error::OpeningInPast => write!(f, "The tender has to open in the future"),
+            Error::DeliveryDueBeforeOpening => write!(f, "The deliveries have to be due after the tender opens"),
+
+            Error::Paused => write!(f, "The creation of new tenders is paused"),
+            Error::CircuitBreakerTripped => write!(f, "The Tenderbox platform is paused by the circuit breaker"),
+            Error::TenderAlreadyExists => write!(f, "The tender account ID already exists"),
+            Error::MilestoneTemplateNotFound => write!(f, "The milestone template doesn't exist"),
+            Error::NoParameterRegistry => write!(f, "The factory has no parameter registry"),
+            Error::ProposalNotFound => write!(f, "The proposal doesn't exist"),
+            Error::ProposalExpired => write!(f, "The proposal has expired"),
+            Error::ProposalAlreadyConfirmed => write!(f, "The proposal is already confirmed by this member"),
+            Error::NoCouncil => write!(f, "The factory has no council"),
+            Error::NoApprovedFactoryCode => write!(f, "No factory code is approved for the upgrade"),
+            Error::CodeHashMismatch => write!(f, "The code doesn't match the approved code hash"),
+            Error::CertificatesAlreadyMinted => write!(f, "The certificates of this tender are already minted"),
+            Error::CertificatesNonTransferable => write!(f, "Completion certificates are non-transferable"),
+            Error::SupplierNotRegistered => write!(f, "The supplier is not registered"),
+            Error::NoBondToWithdraw => write!(f, "There is no bond to withdraw"),
+            Error::BondUnbonding => write!(f, "The bond is still in the unbonding period"),
+            Error::EndorserNotRegistered => write!(f, "Only registered suppliers can endorse"),
+            Error::SelfEndorsement => write!(f, "Suppliers can't endorse themselves"),
+            Error::CategoryNotOffered(code) => {
+                write!(f, "The supplier doesn't offer the category code {}", code)
+            }
+            Error::EndorsementLimitReached => write!(f, "The maximum number of endorsements given is reached"),
+            Error::TotalValueOverflow => write!(f, "The total value delivered overflowed"),
+            Error::SupplierOutOfScope => write!(f, "The supplier is outside of the verifier's scope"),
+            Error::BondTooSmall => write!(f, "The bond is smaller than the slashed amount"),
+            Error::NoBond => write!(f, "The supplier has no bond"),
+            Error::TokensNotAccepted => write!(f, "The treasury doesn't accept fungible tokens yet"),
+            Error::UnknownToken => write!(f, "The treasury holds no such token"),
+            Error::VersionNotApproved => write!(f, "The version is not approved"),
+            Error::ContractNotManaged => write!(f, "The contract is not managed by the coordinator"),
+            Error::VersionKindMismatch => write!(f, "The version is approved for another kind of contract"),
+            Error::TenderConsentRequired => write!(f, "Tenders have to consent to upgrades themselves"),
+            Error::PromiseResultExpected => write!(f, "Contract expected a result on the callback"),
+            Error::ArbitratorNotFound => write!(f, "The arbitrator is not in the registry"),
+            Error::ArbitratorStillApproved => {
+                write!(f, "The arbitrator has to resign or be removed before withdrawing its stake")
+            }
+            Error::ArbitratorHasOpenCases => write!(f, "The arbitrator still has open cases"),
+            Error::CaseAlreadyOpen => write!(f, "The dispute already has an arbitrator"),
+            Error::CaseNotFound => write!(f, "The dispute has no open case"),
+            Error::NotVerified => write!(f, "The account is neither a verified tender nor a verified factory"),
+            Error::AlreadyVerified => write!(f, "The tender is already verified"),
+            Error::NoReviewBounty => write!(f, "There is no review bounty to withdraw"),
+            Error::FactoryQuotaReached => write!(f, "The factory reached its quota of verified tenders"),
+            Error::PromoCodeAlreadyExists => write!(f, "The promo code already exists"),
+            Error::PromoCodeNotFound => write!(f, "The promo code doesn't exist or was used up"),
+            Error::PromoCodeExpired => write!(f, "The promo code expired"),
+            Error::NoSponsorCredit => write!(f, "No sponsor credit to withdraw"),
+            Error::TenderRecordNotFound => write!(f, "The tender doesn't exist or was archived"),
+            Error::TenderSuspended => write!(f, "The verification of the tender is suspended"),
+            Error::NoVerificationRequest => write!(f, "The tender has no pending verification request"),
+            Error::OutOfFactoryScope => write!(f, "The industry is outside the scope of the factory"),
+            Error::NoTenderReservation => write!(f, "The factory has to reserve the tender before verifying it"),
+
+            Error::NotEnoughDepositForTender => write!(f, "Not enough attached deposit to issue the tender"),
+            Error::NotEnoughDepositForStorage => {
+                write!(f, "Not enough attached deposit to cover the storage of the supplier profile")
+            }
+            Error::NotEnoughDepositForBond => {
+                write!(f, "Not enough attached deposit to lock the registration bond")
+            }
+            Error::NotEnoughTreasuryBalance => write!(f, "Not enough available balance in the treasury"),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::env;
+
+// Events emitted by the Tenderbox contracts. Every event carries a sequence number that increases
+// by one per event of the emitting contract, so consumers can detect events they missed.
+
+/// Sequence of the events emitted by a contract.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct EventLog {
+    last_seq: u64,
+}
+
+impl EventLog {
+    pub fn new() -> Self {
+        Self { last_seq: 0 }
+    }
+
+    /// Emits the given event as a log prefixed with its sequence number, e.g. `#42 The tender ...`.
+    pub fn emit(&mut self, message: &[u8]) {
+        self.last_seq += 1;
+        let mut log = format!("#{} ", self.last_seq).into_bytes();
+        log.extend_from_slice(message);
+        env::log(&log);
+    }
+
+    /// Returns the sequence number of the last emitted event, `0` if none was emitted yet.
+    pub fn last_seq(&self) -> u64 {
+        self.last_seq
+    }
+}
@@ -0,0 +1,54 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::{env, AccountId};
+
+use crate::errors::*;
+
+// The Tenderbox foundation role shared by the Tenderbox contracts. The role is handed over in two
+// steps, so a mistyped account ID, e.g. of a Sputnik DAO, cannot lock the foundation out.
+
+/// The account holding the Tenderbox foundation role and the account proposed to take it over.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct FoundationRole {
+    account_id: AccountId,
+    pending_account_id: Option<AccountId>,
+}
+
+impl FoundationRole {
+    pub fn new(account_id: AccountId) -> Self {
+        Self {
+            account_id,
+            pending_account_id: None,
+        }
+    }
+
+    /// Returns the account ID holding the Tenderbox foundation role.
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    /// Returns `true` if the given account ID holds the Tenderbox foundation role.
+    pub fn is(&self, account_id: &AccountId) -> bool {
+        &self.account_id == account_id
+    }
+
+    /// Verifies the predecessor holds the Tenderbox foundation role.
+    pub fn assert_called_by(&self) {
+        require!(self.is(&env::predecessor_account_id()), Error::NotFoundation);
+    }
+
+    /// Proposes the given account ID to take over the role, replacing a previous proposal.
+    /// The caller is expected to have checked the predecessor holds the role.
+    pub fn propose(&mut self, account_id: AccountId) {
+        require!(env::is_valid_account_id(account_id.as_bytes()), Error::InvalidAccountId);
+        self.pending_account_id = Some(account_id);
+    }
+
+    /// Hands the role over to the predecessor if it was proposed.
+    pub fn accept(&mut self) {
+        require!(
+            self.pending_account_id.as_ref() == Some(&env::predecessor_account_id()),
+            Error::NotProposedFoundation
+        );
+        self.account_id = self.pending_account_id.take().unwrap();
+    }
+}
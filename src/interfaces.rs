@@ -0,0 +1,106 @@
+#![allow(dead_code)]
+
+use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::{ext_contract, AccountId, Promise};
+
+use crate::types::*;
+
+// Interfaces of the Tenderbox contracts called across contracts. Every contract includes this module
+// next to the types module instead of declaring its own copy of the interfaces it calls, so a
+// renamed method or argument can't silently break a caller. The callbacks to self stay with each
+// contract.
+
+/// External interface for the Verify Tender contract.
+#[ext_contract(ext_verify_tender)]
+pub trait ExtVerifyTender {
+    fn add_tender(&mut self, tender_account_id: AccountId, code_hash: Option<Base64VecU8>) -> bool;
+
+    fn revalidate_tender(&mut self, tender_account_id: AccountId, code_hash: Base64VecU8) -> bool;
+
+    fn reserve_tender(&mut self, tender_account_id: AccountId, industry: String) -> bool;
+
+    fn release_tender(&mut self, tender_account_id: AccountId) -> bool;
+
+    fn is_verified(&self, tender_account_id: AccountId) -> bool;
+
+    fn is_circuit_breaker_tripped(&self) -> bool;
+}
+
+/// External interface for the tenders created by the Tender Factory.
+#[ext_contract(ext_tender)]
+pub trait ExtTender {
+    fn delete_tender(&mut self, beneficiary_id: AccountId);
+}
+
+/// External interface for the protocol treasury contract.
+#[ext_contract(ext_treasury)]
+pub trait ExtTreasury {
+    fn deposit(&mut self, kind: FeeKind, memo: Option<String>);
+}
+
+/// External interface for the parameter registry contract.
+#[ext_contract(ext_parameter_registry)]
+pub trait ExtParameterRegistry {
+    fn get_parameters(&self) -> PlatformParameters;
+
+    fn is_token_approved(&self, token_account_id: AccountId) -> bool;
+}
+
+/// External interface for the supplier registry contract.
+#[ext_contract(ext_supplier_registry)]
+pub trait ExtSupplierRegistry {
+    fn has_capacity(&self, supplier_account_id: AccountId, quantity: u64) -> bool;
+}
+
+/// External interface for the contracts deployed by the upgrade coordinator.
+#[ext_contract(ext_upgradable)]
+pub trait ExtUpgradable {
+    fn upgrade(&mut self, code: Base64VecU8) -> Promise;
+}
+
+/// External interface for the NEP-141 fungible token contracts.
+#[ext_contract(ext_ft)]
+pub trait ExtFungibleToken {
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
+}
+
+/// The amount of Gas the callers attach to the calls of the interfaces above, by interface.
+/// The callbacks to self stay with each contract.
+pub mod gas {
+    use near_sdk::Gas;
+
+    /// The base amount of gas for a regular execution.
+    pub const BASE: Gas = 25_000_000_000_000;
+
+    pub mod verify_tender {
+        use super::*;
+
+        /// The amount of Gas attached to `is_verified`.
+        /// The base for the execution.
+        pub const IS_VERIFIED: Gas = BASE;
+    }
+
+    pub mod parameter_registry {
+        use super::*;
+
+        /// The amount of Gas attached to `is_token_approved`.
+        /// The base for the execution.
+        pub const IS_TOKEN_APPROVED: Gas = BASE;
+    }
+
+    pub mod upgradable {
+        use super::*;
+
+        /// The amount of Gas attached to the upgrade of a single contract.
+        /// The base for the execution, the base for deploying the code and the base for migrating the state.
+        pub const UPGRADE: Gas = BASE * 3;
+    }
+
+    pub mod ft {
+        use super::*;
+
+        /// The amount of Gas attached to `ft_transfer`.
+        /// The base for the execution.
+        pub const FT_TRANSFER: Gas = BASE;
+    }
+}
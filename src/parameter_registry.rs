@@ -0,0 +1,253 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::UnorderedSet;
+use near_sdk::json_types::U64;
+use near_sdk::{env, near_bindgen, AccountId};
+
+
+// The parameter registry holds the platform-wide parameters of the Tenderbox contracts. Factories
+// and tenders read and cache them, so tuning the platform doesn't require upgrading every contract.
+
+#[macro_use]
+mod errors;
+use crate::errors::*;
+
+mod foundation;
+use crate::foundation::*;
+
+mod timelock;
+use crate::timelock::*;
+
+mod types;
+use crate::types::*;
+
+mod events;
+use crate::events::*;
+
+mod storage;
+use crate::storage::*;
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ParameterRegistry {
+    /// The Tenderbox foundation role. It sets the parameters.
+    pub foundation: FoundationRole,
+
+    /// The sequence of the events emitted by the contract.
+    pub events: EventLog,
+
+    /// The platform-wide parameters.
+    pub parameters: Timelocked<PlatformParameters>,
+
+    /// The delay configuration changes wait before taking effect, in nanoseconds.
+    pub timelock_delay: Timelocked<u64>,
+
+    /// The account IDs of the NEP-141 fungible token contracts, e.g. stablecoins, accepted for fees,
+    /// bonds and deposits across the platform.
+    pub approved_tokens: UnorderedSet<AccountId>,
+}
+
+impl Default for ParameterRegistry {
+    fn default() -> Self {
+        Error::NotInitialized.panic()
+    }
+}
+
+#[near_bindgen]
+impl ParameterRegistry {
+    /// Initializes the registry with the given Tenderbox foundation account ID and initial parameters.
+    #[init]
+    pub fn new(foundation_account_id: AccountId, parameters: PlatformParameters) -> Self {
+        require!(!env::state_exists(), Error::AlreadyInitialized);
+        require!(
+            env::is_valid_account_id(foundation_account_id.as_bytes()),
+            Error::InvalidFoundationAccountId
+        );
+        parameters.assert_valid();
+        Self {
+            foundation: FoundationRole::new(foundation_account_id),
+            events: EventLog::new(),
+            parameters: Timelocked::new(parameters),
+            timelock_delay: Timelocked::new(DEFAULT_TIMELOCK_DELAY),
+            approved_tokens: UnorderedSet::new(StorageKey::ApprovedTokens),
+        }
+    }
+
+    /// Returns the account ID of the Tenderbox foundation.
+    pub fn get_foundation_account_id(&self) -> AccountId {
+        self.foundation.account_id().clone()
+    }
+
+    /// Returns the sequence number of the last event emitted by the contract, `0` if none was emitted yet.
+    pub fn get_last_event_seq(&self) -> u64 {
+        self.events.last_seq()
+    }
+
+    /// Returns the parameters in effect.
+    pub fn get_parameters(&self) -> PlatformParameters {
+        self.parameters.get()
+    }
+
+    /// Returns the announced parameters that are not in effect yet, if any.
+    pub fn get_pending_parameters(&self) -> Option<PendingChange<PlatformParameters>> {
+        self.parameters.pending()
+    }
+
+    /// Returns `true` if the given fungible token is accepted for payments on the platform.
+    pub fn is_token_approved(&self, token_account_id: AccountId) -> bool {
+        self.approved_tokens.contains(&token_account_id)
+    }
+
+    /// Returns up to `limit` approved fungible tokens, starting at `from_index`.
+    pub fn get_approved_tokens(&self, from_index: u64, limit: u64) -> Vec<AccountId> {
+        let tokens = self.approved_tokens.as_vector();
+        (from_index..std::cmp::min(from_index + limit, tokens.len()))
+            .map(|index| tokens.get(index).unwrap())
+            .collect()
+    }
+
+    /**************/
+    /* Tenderbox Foundation */
+    /**************/
+
+    /// Approves the given fungible token for payments on the platform. Returns `true` if it was not approved yet.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn add_approved_token(&mut self, token_account_id: AccountId) -> bool {
+        self.assert_called_by_foundation();
+        require!(env::is_valid_account_id(token_account_id.as_bytes()), Error::InvalidAccountId);
+        self.approved_tokens.insert(&token_account_id)
+    }
+
+    /// Revokes the approval of the given fungible token. Returns `true` if it was approved.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn remove_approved_token(&mut self, token_account_id: AccountId) -> bool {
+        self.assert_called_by_foundation();
+        self.approved_tokens.remove(&token_account_id)
+    }
+
+    /// Announces new parameters, in effect after the timelock delay.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn set_parameters(&mut self, parameters: PlatformParameters) {
+        self.assert_called_by_foundation();
+        parameters.assert_valid();
+        let effective_at = self
+            .parameters
+            .schedule(parameters, self.timelock_delay.get());
+        self.events.emit(format!("The platform parameters change at {}", effective_at).as_bytes());
+    }
+
+    /// Announces a new timelock delay in nanoseconds, in effect after the current timelock delay.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn set_timelock_delay(&mut self, timelock_delay: U64) {
+        self.assert_called_by_foundation();
+        let effective_at = self
+            .timelock_delay
+            .schedule(timelock_delay.0, self.timelock_delay.get());
+        self.events.emit(
+            format!(
+                "The timelock delay changes to {} at {}",
+                timelock_delay.0, effective_at
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Cancels the configuration changes that are not in effect yet.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn cancel_pending_config(&mut self) {
+        self.assert_called_by_foundation();
+        self.parameters.cancel();
+        self.timelock_delay.cancel();
+        self.events.emit(b"The pending configuration changes are cancelled");
+    }
+
+    /// Proposes the given account ID, e.g. a Sputnik DAO, as the new Tenderbox foundation account.
+    /// The role is only handed over once the proposed account calls `accept_foundation_role`,
+    /// which a DAO does through a function-call proposal.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn propose_foundation_account_id(&mut self, foundation_account_id: AccountId) {
+        self.assert_called_by_foundation();
+        self.foundation.propose(foundation_account_id);
+    }
+
+    /// Accepts the Tenderbox foundation role proposed to the predecessor.
+    /// This method can only be called by the account proposed with `propose_foundation_account_id`.
+    pub fn accept_foundation_role(&mut self) {
+        self.foundation.accept();
+        self.events.emit(
+            format!(
+                "The Tenderbox foundation role was handed over to @{}",
+                self.foundation.account_id()
+            )
+            .as_bytes(),
+        );
+    }
+
+    /************/
+    /* Internal */
+    /************/
+
+    /// Internal method to verify the predecessor was the Tenderbox Foundation account ID.
+    fn assert_called_by_foundation(&self) {
+        self.foundation.assert_called_by();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::{testing_env, MockedBlockchain};
+
+    mod test_utils;
+    use test_utils::*;
+
+    fn parameters() -> PlatformParameters {
+        PlatformParameters {
+            min_attached_balance: ntoy(30).into(),
+            posting_fee: ntoy(1).into(),
+            award_fee_bps: 50,
+            tender_new_gas: 50_000_000_000_000.into(),
+            dispute_window: (7 * 24 * 60 * 60 * 1_000_000_000u64).into(),
+            emergency_submission_window: (24 * 60 * 60 * 1_000_000_000u64).into(),
+            emergency_challenge_window: (24 * 60 * 60 * 1_000_000_000u64).into(),
+        }
+    }
+
+    #[test]
+    fn test_timelocked_parameters() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_parameter_registry())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = ParameterRegistry::new(account_tenderbox(), parameters());
+        let mut new_parameters = parameters();
+        new_parameters.posting_fee = ntoy(2).into();
+        contract.set_parameters(new_parameters.clone());
+
+        // Still the old parameters before the timelock delay passed
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_parameters(), parameters());
+        assert!(contract.get_pending_parameters().is_some());
+
+        context.block_timestamp += DEFAULT_TIMELOCK_DELAY;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_parameters(), new_parameters);
+    }
+
+    #[test]
+    #[should_panic(expected = "E403: The award fee can't be above 10000 basis points")]
+    fn test_award_fee_above_total() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_parameter_registry())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = ParameterRegistry::new(account_tenderbox(), parameters());
+        let mut new_parameters = parameters();
+        new_parameters.award_fee_bps = 10_001;
+        contract.set_parameters(new_parameters);
+    }
+}
@@ -0,0 +1,76 @@
+#![allow(dead_code)]
+
+use near_sdk::borsh::{self, BorshSerialize};
+use near_sdk::{AccountId, BorshStorageKey};
+
+// Storage prefixes of the collections of the Tenderbox contracts. Every contract includes this
+// module and creates its collections from these keys instead of byte literals, so two collections
+// can never share a prefix. A key is stored as its Borsh-encoded variant index: only ever append
+// new variants, since reordering or removing one moves the state of the collections after it.
+
+#[derive(BorshSerialize, BorshStorageKey)]
+pub enum StorageKey {
+    // Verify Tender
+    VerifiedTenders,
+    VerifiedFactories,
+    VerifiedSuppliers,
+    Attesters,
+    // Tender factory
+    Tenders,
+    ArchivedTenders,
+    TenderRecords,
+    MilestoneTemplates,
+    CouncilProposals,
+    // Parameter registry
+    ApprovedTokens,
+    // Supplier registry
+    Suppliers,
+    Categories,
+    SuppliersByCategory,
+    SuppliersInCategory { code: u32 },
+    Verifiers,
+    VerifiedSupplierProfiles,
+    Blacklist,
+    Reputations,
+    Unbonding,
+    Endorsements,
+    EndorsementCounts,
+    EndorsementsGiven,
+    // Treasury
+    Disbursements,
+    TokenAccounting,
+    // Upgrade coordinator
+    CodeVersions,
+    ManagedContracts,
+    // Certificates
+    CertificateTokens,
+    CertificatesPerOwner,
+    CertificatesOfOwner { owner_id: AccountId },
+    // Arbitrator registry
+    Arbitrators,
+    ArbitrationCases,
+    // Verify Tender, continued
+    TenderCodeHashes,
+    SuspendedTenders,
+    VerificationMetadata,
+    ReviewBounties,
+    FactoryRecords,
+    // Tender factory, continued
+    CompletedTendersByIssuer,
+    PromoCodes,
+    SponsorCredits,
+    ClosedTenders,
+    ArchivedTenderRecords,
+    // Verify Tender, continued
+    ImportCandidates,
+    TenderFactories,
+    VerificationRequests,
+    TenderVerifiers,
+    ReservedTenders,
+    // Tender factory, continued
+    FactoryOperatorFeeSplit,
+    FactoryGasConfig,
+    FactoryCachedParameters,
+    FactoryCouncil,
+    FactoryFeeDiscountTiers,
+}
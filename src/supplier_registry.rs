@@ -0,0 +1,1912 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LookupMap, LookupSet, UnorderedMap, UnorderedSet};
+use near_sdk::json_types::{Base64VecU8, U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, ext_contract, near_bindgen, AccountId, Balance, Promise};
+
+
+// The supplier registry keeps the public profiles of suppliers bidding on Tenderbox tenders.
+// Tenders use it for prequalification checks and frontends to look up and invite suppliers.
+
+#[macro_use]
+mod errors;
+use crate::errors::*;
+
+mod foundation;
+use crate::foundation::*;
+
+mod timelock;
+use crate::timelock::*;
+
+mod types;
+
+mod interfaces;
+use crate::interfaces::*;
+
+mod events;
+use crate::events::*;
+
+mod storage;
+use crate::storage::*;
+
+mod utils;
+use crate::utils::*;
+
+
+// The bond a supplier has to lock when registering, until the foundation changes it.
+const DEFAULT_SUPPLIER_BOND: Balance = 10_000_000_000_000_000_000_000_000;
+
+// The time a bond stays locked after the supplier unregistered, so it can still be slashed. 14 days.
+const UNBONDING_PERIOD: u64 = 14 * 24 * 60 * 60 * 1_000_000_000;
+
+// The maximum number of endorsements a supplier can have given at the same time.
+const MAX_ENDORSEMENTS_GIVEN: u64 = 20;
+
+pub mod gas {
+    use near_sdk::Gas;
+
+    pub use crate::interfaces::gas::*;
+
+    /// The amount of Gas the contract will attach to the callback to itself.
+    /// The base for the execution and the base for updating the reputation record.
+    pub const CALLBACK: Gas = BASE * 2;
+}
+
+/// Public profile of a registered supplier.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SupplierProfile {
+    /// The sha256 hash of the registered company name.
+    pub company_name_hash: Base64VecU8,
+    /// Industries/Sectors the supplier operates in.
+    pub industries: Vec<String>,
+    /// Regions the supplier can deliver to.
+    pub regions: Vec<String>,
+    /// Standardized (UNSPSC-style) category codes of the products/services the supplier offers.
+    pub categories: Vec<u32>,
+    /// Production/delivery capacity in units of product per month.
+    pub capacity: u64,
+}
+
+/// Supplier entry returned by the discovery views.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SupplierView {
+    /// The account ID of the supplier.
+    pub account_id: AccountId,
+    /// The public profile of the supplier.
+    pub profile: SupplierProfile,
+    /// Whether the supplier is verified by the foundation or by an appointed verifier.
+    pub verified: bool,
+    /// Whether the supplier is currently blacklisted.
+    pub blacklisted: bool,
+}
+
+/// Scope within which a delegated verifier organization can verify suppliers.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VerifierScope {
+    /// Industries the verifier covers. A supplier has to operate in one of them.
+    pub industries: Vec<String>,
+    /// Regions the verifier covers. A supplier has to deliver to one of them.
+    pub regions: Vec<String>,
+}
+
+/// Foundation decision excluding a supplier from bidding for a period.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BlacklistEntry {
+    /// The justification of the decision.
+    pub reason: String,
+    /// The block timestamp in nanoseconds until which the supplier is blacklisted.
+    pub until: U64,
+}
+
+/// Aggregated track record of a supplier across all verified tenders.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Reputation {
+    /// Number of tenders awarded to the supplier.
+    pub tenders_won: u64,
+    /// Number of awarded tenders the supplier completed by the agreed deadline.
+    pub completed_on_time: u64,
+    /// Number of awarded tenders the supplier completed after the agreed deadline.
+    pub completed_late: u64,
+    /// Number of disputes resolved against the supplier.
+    pub disputes_lost: u64,
+    /// Penalty points of the rulings against the supplier, lowering its standing with issuers.
+    pub penalty_points: u64,
+    /// Total value of the completed tenders.
+    pub total_value_delivered: U128,
+}
+
+/// Outcome reported by a tender contract about one of its suppliers.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ReputationReport {
+    /// The tender was awarded to the supplier.
+    TenderWon,
+    /// The supplier completed the tender with the given value.
+    TenderCompleted { on_time: bool, value: U128 },
+    /// A dispute on the tender was resolved against the supplier, with the penalty points of the ruling.
+    DisputeLost { penalty_points: u32 },
+}
+
+/// Foundation policy suspending suppliers that keep losing disputes.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SuspensionPolicy {
+    /// Number of disputes lost from which a supplier is suspended on every further loss.
+    pub max_disputes_lost: u64,
+    /// Time in nanoseconds a suspension lasts.
+    pub period: U64,
+}
+
+/// Stored registry record of a supplier.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Supplier {
+    /// The public profile of the supplier.
+    pub profile: SupplierProfile,
+    /// The amount the supplier paid for the storage of its record. Refunded on unregistering.
+    pub storage_deposit: Balance,
+    /// The registration bond locked by the supplier. Can be slashed by arbitration outcomes.
+    pub bond: Balance,
+}
+
+/// Bond of an unregistered supplier waiting for the end of the unbonding period.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct UnbondingBond {
+    /// The remaining bond.
+    pub amount: Balance,
+    /// The block timestamp in nanoseconds from which the bond can be withdrawn.
+    pub release_at: u64,
+}
+
+impl UnbondingBond {
+    /// Returns `true` if the unbonding period is over at the given block timestamp.
+    pub fn is_released_at(&self, block_timestamp: u64) -> bool {
+        block_timestamp >= self.release_at
+    }
+}
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SupplierRegistry {
+    /// The Tenderbox foundation role. It manages the registry.
+    pub foundation: FoundationRole,
+
+    /// The registered suppliers by account ID.
+    pub suppliers: UnorderedMap<AccountId, Supplier>,
+
+    /// The standardized category taxonomy managed by the foundation, as category code to name.
+    pub categories: UnorderedMap<u32, String>,
+
+    /// The index of registered suppliers by the category codes they offer.
+    pub suppliers_by_category: LookupMap<u32, UnorderedSet<AccountId>>,
+
+    /// The verifier organizations appointed by the foundation, with the scope they can verify suppliers in.
+    pub verifiers: UnorderedMap<AccountId, VerifierScope>,
+
+    /// The verified suppliers, with the account ID that verified them.
+    pub verified_suppliers: LookupMap<AccountId, AccountId>,
+
+    /// The blacklisted supplier account IDs. Tenders reject their bids while the entry is active.
+    pub blacklist: LookupMap<AccountId, BlacklistEntry>,
+
+    /// The account ID of the verify tender contract. Reputation reports are only accepted from tenders verified there.
+    pub verify_tender_account_id: AccountId,
+
+    /// The reputation records of suppliers that verified tenders reported on.
+    pub reputations: LookupMap<AccountId, Reputation>,
+
+    /// The bond a supplier has to lock when registering and keep to continue bidding.
+    pub supplier_bond: Timelocked<Balance>,
+
+    /// The delay configuration changes wait before taking effect, in nanoseconds.
+    pub timelock_delay: Timelocked<u64>,
+
+    /// The bonds of unregistered suppliers that are still in the unbonding period.
+    pub unbonding: LookupMap<AccountId, UnbondingBond>,
+
+    /// The endorsements between suppliers, as (endorser, endorsed supplier, category code).
+    pub endorsements: LookupSet<(AccountId, AccountId, u32)>,
+
+    /// The number of endorsements a supplier received per category code.
+    pub endorsement_counts: LookupMap<(AccountId, u32), u64>,
+
+    /// The number of endorsements a supplier has currently given.
+    pub endorsements_given: LookupMap<AccountId, u64>,
+
+    /// The sequence of the events emitted by the contract.
+    pub events: EventLog,
+
+    /// The policy suspending suppliers after repeated adverse rulings, if any.
+    pub suspension_policy: Option<SuspensionPolicy>,
+}
+
+/// External interface for the callbacks to self.
+#[ext_contract(ext_self)]
+pub trait ExtSelf {
+    fn on_report_authenticated(
+        &mut self,
+        tender_account_id: AccountId,
+        supplier_account_id: AccountId,
+        report: ReputationReport,
+    ) -> bool;
+
+    fn on_slash_authenticated(
+        &mut self,
+        tender_account_id: AccountId,
+        supplier_account_id: AccountId,
+        amount: U128,
+        beneficiary_account_id: AccountId,
+    ) -> bool;
+}
+
+impl Default for SupplierRegistry {
+    fn default() -> Self {
+        Error::NotInitialized.panic()
+    }
+}
+
+#[near_bindgen]
+impl SupplierRegistry {
+    /// Initializes the registry with the given Tenderbox foundation account ID and the account ID
+    /// of the verify tender contract.
+    #[init]
+    pub fn new(foundation_account_id: AccountId, verify_tender_account_id: AccountId) -> Self {
+        require!(!env::state_exists(), Error::AlreadyInitialized);
+        require!(
+            env::is_valid_account_id(foundation_account_id.as_bytes()),
+            Error::InvalidFoundationAccountId
+        );
+        require!(
+            env::is_valid_account_id(verify_tender_account_id.as_bytes()),
+            Error::InvalidVerifyTenderAccountId
+        );
+        Self {
+            foundation: FoundationRole::new(foundation_account_id),
+            suppliers: UnorderedMap::new(StorageKey::Suppliers),
+            categories: UnorderedMap::new(StorageKey::Categories),
+            suppliers_by_category: LookupMap::new(StorageKey::SuppliersByCategory),
+            verifiers: UnorderedMap::new(StorageKey::Verifiers),
+            verified_suppliers: LookupMap::new(StorageKey::VerifiedSupplierProfiles),
+            blacklist: LookupMap::new(StorageKey::Blacklist),
+            verify_tender_account_id,
+            reputations: LookupMap::new(StorageKey::Reputations),
+            supplier_bond: Timelocked::new(DEFAULT_SUPPLIER_BOND),
+            timelock_delay: Timelocked::new(DEFAULT_TIMELOCK_DELAY),
+            unbonding: LookupMap::new(StorageKey::Unbonding),
+            endorsements: LookupSet::new(StorageKey::Endorsements),
+            endorsement_counts: LookupMap::new(StorageKey::EndorsementCounts),
+            endorsements_given: LookupMap::new(StorageKey::EndorsementsGiven),
+            events: EventLog::new(),
+            suspension_policy: None,
+        }
+    }
+
+    /// Returns the account ID of the Tenderbox foundation.
+    pub fn get_foundation_account_id(&self) -> AccountId {
+        self.foundation.account_id().clone()
+    }
+
+    /// Returns the sequence number of the last event emitted by the contract, `0` if none was emitted yet.
+    pub fn get_last_event_seq(&self) -> u64 {
+        self.events.last_seq()
+    }
+
+    /// Returns `true` if the given account ID is a registered supplier.
+    pub fn is_registered(&self, supplier_account_id: AccountId) -> bool {
+        require!(env::is_valid_account_id(supplier_account_id.as_bytes()), Error::InvalidAccountId);
+        self.suppliers.get(&supplier_account_id).is_some()
+    }
+
+    /// Returns the profile of the given supplier, if registered.
+    pub fn get_supplier(&self, supplier_account_id: AccountId) -> Option<SupplierProfile> {
+        require!(env::is_valid_account_id(supplier_account_id.as_bytes()), Error::InvalidAccountId);
+        self.suppliers
+            .get(&supplier_account_id)
+            .map(|supplier| supplier.profile)
+    }
+
+    /// Returns the total number of registered suppliers.
+    pub fn get_number_of_suppliers(&self) -> u64 {
+        self.suppliers.len()
+    }
+
+    /// Returns up to `limit` registered suppliers, starting at `from_index`.
+    pub fn get_suppliers(&self, from_index: u64, limit: u64) -> Vec<SupplierView> {
+        self.internal_get_suppliers(from_index, limit, |_| true)
+    }
+
+    /// Returns the verified suppliers among the registered suppliers from `from_index` to `from_index + limit`.
+    /// Frontends page through the whole registry by advancing `from_index` by `limit`.
+    pub fn get_verified_suppliers(&self, from_index: u64, limit: u64) -> Vec<SupplierView> {
+        self.internal_get_suppliers(from_index, limit, |supplier| supplier.verified)
+    }
+
+    /// Returns the suppliers operating in the given industry among the registered suppliers
+    /// from `from_index` to `from_index + limit`.
+    pub fn get_suppliers_by_industry(
+        &self,
+        industry: String,
+        from_index: u64,
+        limit: u64,
+    ) -> Vec<SupplierView> {
+        self.internal_get_suppliers(from_index, limit, |supplier| {
+            supplier.profile.industries.contains(&industry)
+        })
+    }
+
+    /// Returns the suppliers delivering to the given region among the registered suppliers
+    /// from `from_index` to `from_index + limit`.
+    pub fn get_suppliers_by_region(
+        &self,
+        region: String,
+        from_index: u64,
+        limit: u64,
+    ) -> Vec<SupplierView> {
+        self.internal_get_suppliers(from_index, limit, |supplier| {
+            supplier.profile.regions.contains(&region)
+        })
+    }
+
+    /// Returns the name of the given category code, if it is part of the taxonomy.
+    pub fn get_category(&self, code: u32) -> Option<String> {
+        self.categories.get(&code)
+    }
+
+    /// Returns up to `limit` categories of the taxonomy as (code, name), starting at `from_index`.
+    pub fn get_categories(&self, from_index: u64, limit: u64) -> Vec<(u32, String)> {
+        let keys = self.categories.keys_as_vector();
+        let values = self.categories.values_as_vector();
+        (from_index..std::cmp::min(from_index + limit, keys.len()))
+            .map(|index| (keys.get(index).unwrap(), values.get(index).unwrap()))
+            .collect()
+    }
+
+    /// Returns up to `limit` account IDs of suppliers offering the given category code, starting at `from_index`.
+    pub fn get_suppliers_by_category(&self, code: u32, from_index: u64, limit: u64) -> Vec<AccountId> {
+        match self.suppliers_by_category.get(&code) {
+            Some(suppliers) => {
+                let suppliers = suppliers.as_vector();
+                (from_index..std::cmp::min(from_index + limit, suppliers.len()))
+                    .map(|index| suppliers.get(index).unwrap())
+                    .collect()
+            }
+            None => vec![],
+        }
+    }
+
+    /// Returns the scope of the given verifier organization, if appointed.
+    pub fn get_verifier(&self, verifier_account_id: AccountId) -> Option<VerifierScope> {
+        self.verifiers.get(&verifier_account_id)
+    }
+
+    /// Returns `true` if the given supplier is verified by the foundation or by a currently appointed verifier.
+    pub fn is_supplier_verified(&self, supplier_account_id: AccountId) -> bool {
+        self.get_supplier_verifier(supplier_account_id).is_some()
+    }
+
+    /// Returns the capacity declared by the given supplier, if its profile is verified.
+    /// A changed profile loses its verification, so the capacity returned is always the verified one.
+    pub fn get_verified_capacity(&self, supplier_account_id: AccountId) -> Option<u64> {
+        if !self.is_supplier_verified(supplier_account_id.clone()) {
+            return None;
+        }
+        self.suppliers
+            .get(&supplier_account_id)
+            .map(|supplier| supplier.profile.capacity)
+    }
+
+    /// Returns `true` if the given supplier is verified with a capacity covering the given quantity.
+    /// Tenders reject bids on quantities the bidder can't deliver.
+    pub fn has_capacity(&self, supplier_account_id: AccountId, quantity: u64) -> bool {
+        self.get_verified_capacity(supplier_account_id)
+            .map(|capacity| capacity >= quantity)
+            .unwrap_or(false)
+    }
+
+    /// Returns the account ID that verified the given supplier, if the verification is still valid.
+    pub fn get_supplier_verifier(&self, supplier_account_id: AccountId) -> Option<AccountId> {
+        require!(env::is_valid_account_id(supplier_account_id.as_bytes()), Error::InvalidAccountId);
+        self.verified_suppliers
+            .get(&supplier_account_id)
+            .filter(|verifier_account_id| {
+                self.foundation.is(verifier_account_id)
+                    || self.verifiers.get(verifier_account_id).is_some()
+            })
+    }
+
+    /// Returns `true` if the given account ID is currently blacklisted.
+    pub fn is_blacklisted(&self, supplier_account_id: AccountId) -> bool {
+        self.get_blacklist_entry(supplier_account_id)
+            .map(|entry| entry.until.0 > env::block_timestamp())
+            .unwrap_or(false)
+    }
+
+    /// Returns the blacklist entry of the given account ID, if it was ever blacklisted and not cleared.
+    pub fn get_blacklist_entry(&self, supplier_account_id: AccountId) -> Option<BlacklistEntry> {
+        require!(env::is_valid_account_id(supplier_account_id.as_bytes()), Error::InvalidAccountId);
+        self.blacklist.get(&supplier_account_id)
+    }
+
+    /// Returns the policy suspending suppliers after repeated adverse rulings, if any.
+    pub fn get_suspension_policy(&self) -> Option<SuspensionPolicy> {
+        self.suspension_policy.clone()
+    }
+
+    /// Returns the reputation record of the given supplier. Suppliers without reports have an empty record.
+    pub fn get_reputation(&self, supplier_account_id: AccountId) -> Reputation {
+        require!(env::is_valid_account_id(supplier_account_id.as_bytes()), Error::InvalidAccountId);
+        self.reputations
+            .get(&supplier_account_id)
+            .unwrap_or_default()
+    }
+
+    /// Returns the bond a supplier has to lock when registering and keep to continue bidding.
+    pub fn get_supplier_bond(&self) -> U128 {
+        self.supplier_bond.get().into()
+    }
+
+    /// Returns the announced change of the supplier bond that is not in effect yet, if any.
+    pub fn get_pending_supplier_bond(&self) -> Option<PendingChange<U128>> {
+        self.supplier_bond.pending().map(|change| PendingChange {
+            value: change.value.into(),
+            effective_at: change.effective_at,
+        })
+    }
+
+    /// Returns the registration bond currently locked by the given supplier.
+    pub fn get_bond(&self, supplier_account_id: AccountId) -> U128 {
+        self.suppliers
+            .get(&supplier_account_id)
+            .map(|supplier| supplier.bond)
+            .unwrap_or(0)
+            .into()
+    }
+
+    /// Returns `true` if the given account ID is a registered supplier whose bond covers the current requirement.
+    /// Tenders reject bids from suppliers that have to top up their bond.
+    pub fn has_sufficient_bond(&self, supplier_account_id: AccountId) -> bool {
+        self.suppliers
+            .get(&supplier_account_id)
+            .map(|supplier| supplier.bond >= self.supplier_bond.get())
+            .unwrap_or(false)
+    }
+
+    /// Returns the number of endorsements the given supplier received for the given category code.
+    pub fn get_endorsement_count(&self, supplier_account_id: AccountId, code: u32) -> u64 {
+        self.endorsement_counts
+            .get(&(supplier_account_id, code))
+            .unwrap_or(0)
+    }
+
+    /// Returns `true` if the endorser currently endorses the given supplier for the given category code.
+    pub fn has_endorsed(
+        &self,
+        endorser_account_id: AccountId,
+        supplier_account_id: AccountId,
+        code: u32,
+    ) -> bool {
+        self.endorsements
+            .contains(&(endorser_account_id, supplier_account_id, code))
+    }
+
+    /************/
+    /* Supplier */
+    /************/
+
+    /// Registers the predecessor as a supplier with the given profile, or updates its profile.
+    /// The attached deposit has to cover the storage of the record and, for a new registration,
+    /// the registration bond. A previously paid storage deposit and a bond still in the unbonding
+    /// period are counted towards them and any excess is refunded.
+    /// Returns `true` if the supplier was not registered before, `false` otherwise.
+    #[payable]
+    pub fn register_supplier(&mut self, profile: SupplierProfile) -> bool {
+        require!(profile.company_name_hash.0.len() == 32, Error::InvalidCompanyNameHash);
+        for code in profile.categories.iter() {
+            require!(self.categories.get(code).is_some(), Error::UnknownCategoryCode(*code));
+        }
+        let supplier_account_id = env::predecessor_account_id();
+        // A changed profile has to be verified again.
+        let previous_supplier = self.internal_remove_supplier(&supplier_account_id);
+        let previous_deposit = previous_supplier
+            .as_ref()
+            .map(|supplier| supplier.storage_deposit)
+            .unwrap_or(0);
+        let (previous_bond, bond_due) = match previous_supplier.as_ref() {
+            Some(supplier) => (supplier.bond, 0),
+            None => {
+                let unbonding_bond = self
+                    .unbonding
+                    .remove(&supplier_account_id)
+                    .map(|unbonding| unbonding.amount)
+                    .unwrap_or(0);
+                (unbonding_bond, self.supplier_bond.get().saturating_sub(unbonding_bond))
+            }
+        };
+
+        let initial_storage_usage = env::storage_usage();
+        for code in profile.categories.iter() {
+            self.internal_index_category(*code, &supplier_account_id);
+        }
+        let mut supplier = Supplier {
+            profile,
+            storage_deposit: 0,
+            bond: previous_bond + bond_due,
+        };
+        self.suppliers.insert(&supplier_account_id, &supplier);
+        let storage_cost = Balance::from(env::storage_usage() - initial_storage_usage)
+            * env::storage_byte_cost();
+
+        let available_deposit = env::attached_deposit() + previous_deposit;
+        require!(available_deposit >= storage_cost, Error::NotEnoughDepositForStorage);
+        require!(available_deposit - storage_cost >= bond_due, Error::NotEnoughDepositForBond);
+        // The deposit is a fixed size field, so updating it doesn't change the storage usage.
+        supplier.storage_deposit = storage_cost;
+        self.suppliers.insert(&supplier_account_id, &supplier);
+
+        let refund = available_deposit - storage_cost - bond_due;
+        if refund > 0 {
+            Promise::new(supplier_account_id).transfer(refund);
+        }
+        previous_supplier.is_none()
+    }
+
+    /// Removes the predecessor from the registry and refunds its storage deposit.
+    /// The registration bond stays locked for the unbonding period and is then withdrawn with `withdraw_bond`.
+    /// Returns `true` if the supplier was registered before, `false` otherwise.
+    pub fn unregister_supplier(&mut self) -> bool {
+        let supplier_account_id = env::predecessor_account_id();
+        match self.internal_remove_supplier(&supplier_account_id) {
+            Some(supplier) => {
+                if supplier.bond > 0 {
+                    self.unbonding.insert(
+                        &supplier_account_id,
+                        &UnbondingBond {
+                            amount: supplier.bond,
+                            release_at: env::block_timestamp() + UNBONDING_PERIOD,
+                        },
+                    );
+                }
+                Promise::new(supplier_account_id).transfer(supplier.storage_deposit);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds the attached deposit to the registration bond of the predecessor.
+    /// Returns the new bond.
+    #[payable]
+    pub fn top_up_bond(&mut self) -> U128 {
+        let supplier_account_id = env::predecessor_account_id();
+        let mut supplier = self
+            .suppliers
+            .get(&supplier_account_id)
+            .unwrap_or_else(|| Error::SupplierNotRegistered.panic());
+        supplier.bond += env::attached_deposit();
+        self.suppliers.insert(&supplier_account_id, &supplier);
+        supplier.bond.into()
+    }
+
+    /// Sends the bond of the unregistered predecessor back once the unbonding period is over.
+    pub fn withdraw_bond(&mut self) -> Promise {
+        let supplier_account_id = env::predecessor_account_id();
+        let unbonding = self
+            .unbonding
+            .get(&supplier_account_id)
+            .unwrap_or_else(|| Error::NoBondToWithdraw.panic());
+        require!(unbonding.is_released_at(env::block_timestamp()), Error::BondUnbonding);
+        self.unbonding.remove(&supplier_account_id);
+        Promise::new(supplier_account_id).transfer(unbonding.amount)
+    }
+
+    /// Endorses the given supplier for one of the category codes it offers.
+    /// Both the predecessor and the endorsed supplier have to be registered and a supplier can have
+    /// at most `MAX_ENDORSEMENTS_GIVEN` endorsements given at the same time.
+    /// Returns `true` if the endorsement was not given before, `false` otherwise.
+    pub fn endorse(&mut self, supplier_account_id: AccountId, code: u32) -> bool {
+        let endorser_account_id = env::predecessor_account_id();
+        require!(self.suppliers.get(&endorser_account_id).is_some(), Error::EndorserNotRegistered);
+        require!(endorser_account_id != supplier_account_id, Error::SelfEndorsement);
+        let supplier = self
+            .suppliers
+            .get(&supplier_account_id)
+            .unwrap_or_else(|| Error::SupplierNotRegistered.panic());
+        require!(supplier.profile.categories.contains(&code), Error::CategoryNotOffered(code));
+        if !self
+            .endorsements
+            .insert(&(endorser_account_id.clone(), supplier_account_id.clone(), code))
+        {
+            return false;
+        }
+        let given = self.endorsements_given.get(&endorser_account_id).unwrap_or(0) + 1;
+        require!(given <= MAX_ENDORSEMENTS_GIVEN, Error::EndorsementLimitReached);
+        self.endorsements_given.insert(&endorser_account_id, &given);
+        let count = self.get_endorsement_count(supplier_account_id.clone(), code) + 1;
+        self.endorsement_counts
+            .insert(&(supplier_account_id, code), &count);
+        true
+    }
+
+    /// Revokes the endorsement of the given supplier for the given category code by the predecessor.
+    /// Returns `true` if the endorsement was given before, `false` otherwise.
+    pub fn revoke_endorsement(&mut self, supplier_account_id: AccountId, code: u32) -> bool {
+        let endorser_account_id = env::predecessor_account_id();
+        if !self
+            .endorsements
+            .remove(&(endorser_account_id.clone(), supplier_account_id.clone(), code))
+        {
+            return false;
+        }
+        let given = self.endorsements_given.get(&endorser_account_id).unwrap_or(0);
+        self.endorsements_given
+            .insert(&endorser_account_id, &given.saturating_sub(1));
+        let count = self.get_endorsement_count(supplier_account_id.clone(), code);
+        self.endorsement_counts
+            .insert(&(supplier_account_id, code), &count.saturating_sub(1));
+        true
+    }
+
+    /**********/
+    /* Tender */
+    /**********/
+
+    /// Reports an outcome about the given supplier from the calling tender.
+    /// The report is only applied once the verify tender contract confirms the predecessor is a verified tender.
+    pub fn report_reputation(
+        &mut self,
+        supplier_account_id: AccountId,
+        report: ReputationReport,
+    ) -> Promise {
+        require!(env::is_valid_account_id(supplier_account_id.as_bytes()), Error::InvalidAccountId);
+        let tender_account_id = env::predecessor_account_id();
+        ext_verify_tender::is_verified(
+            tender_account_id.clone(),
+            &self.verify_tender_account_id,
+            NO_DEPOSIT,
+            gas::verify_tender::IS_VERIFIED,
+        )
+        .then(ext_self::on_report_authenticated(
+            tender_account_id,
+            supplier_account_id,
+            report,
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            gas::CALLBACK,
+        ))
+    }
+
+    /// Callback after the verify tender contract was asked about the reporting tender.
+    /// Applies the report and returns `true` if the tender is verified, returns `false` otherwise.
+    pub fn on_report_authenticated(
+        &mut self,
+        tender_account_id: AccountId,
+        supplier_account_id: AccountId,
+        report: ReputationReport,
+    ) -> bool {
+        assert_self();
+        let tender_verified = promise_result_as_bool();
+        if !tender_verified {
+            self.events.emit(
+                format!(
+                    "Ignoring the reputation report from @{}: it is not a verified tender",
+                    tender_account_id
+                )
+                .as_bytes(),
+            );
+            return false;
+        }
+
+        let mut reputation = self
+            .reputations
+            .get(&supplier_account_id)
+            .unwrap_or_default();
+        match report {
+            ReputationReport::TenderWon => reputation.tenders_won += 1,
+            ReputationReport::TenderCompleted { on_time, value } => {
+                if on_time {
+                    reputation.completed_on_time += 1;
+                } else {
+                    reputation.completed_late += 1;
+                }
+                reputation.total_value_delivered = reputation
+                    .total_value_delivered
+                    .0
+                    .checked_add(value.0)
+                    .unwrap_or_else(|| Error::TotalValueOverflow.panic())
+                    .into();
+            }
+            ReputationReport::DisputeLost { penalty_points } => {
+                reputation.disputes_lost += 1;
+                reputation.penalty_points = reputation
+                    .penalty_points
+                    .saturating_add(penalty_points as u64);
+                self.internal_apply_suspension_policy(&supplier_account_id, reputation.disputes_lost);
+            }
+        }
+        self.reputations.insert(&supplier_account_id, &reputation);
+        self.events.emit(
+            format!(
+                "The tender @{} updated the reputation of the supplier @{}",
+                tender_account_id, supplier_account_id
+            )
+            .as_bytes(),
+        );
+        true
+    }
+
+    /// Slashes the given amount from the bond of the given supplier on behalf of the calling tender, e.g. once
+    /// the arbitration of its dispute was decided against the supplier, and sends it to the given beneficiary.
+    /// The slash is only applied once the verify tender contract confirms the predecessor is a verified tender.
+    pub fn report_slash(
+        &mut self,
+        supplier_account_id: AccountId,
+        amount: U128,
+        beneficiary_account_id: AccountId,
+    ) -> Promise {
+        require!(env::is_valid_account_id(supplier_account_id.as_bytes()), Error::InvalidAccountId);
+        require!(
+            env::is_valid_account_id(beneficiary_account_id.as_bytes()),
+            Error::InvalidBeneficiaryAccountId
+        );
+        let tender_account_id = env::predecessor_account_id();
+        ext_verify_tender::is_verified(
+            tender_account_id.clone(),
+            &self.verify_tender_account_id,
+            NO_DEPOSIT,
+            gas::verify_tender::IS_VERIFIED,
+        )
+        .then(ext_self::on_slash_authenticated(
+            tender_account_id,
+            supplier_account_id,
+            amount,
+            beneficiary_account_id,
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            gas::CALLBACK,
+        ))
+    }
+
+    /// Callback after the verify tender contract was asked about the slashing tender.
+    /// Applies the slash and returns `true` if the tender is verified, returns `false` otherwise.
+    pub fn on_slash_authenticated(
+        &mut self,
+        tender_account_id: AccountId,
+        supplier_account_id: AccountId,
+        amount: U128,
+        beneficiary_account_id: AccountId,
+    ) -> bool {
+        assert_self();
+        let tender_verified = promise_result_as_bool();
+        if !tender_verified {
+            self.events.emit(
+                format!(
+                    "Ignoring the slash from @{}: it is not a verified tender",
+                    tender_account_id
+                )
+                .as_bytes(),
+            );
+            return false;
+        }
+        self.internal_slash_bond(&supplier_account_id, amount.0, beneficiary_account_id);
+        true
+    }
+
+    /************/
+    /* Verifier + Tenderbox Foundation */
+    /************/
+
+    /// Marks the given registered supplier as verified.
+    /// Returns `true` if the supplier was not verified before, `false` otherwise.
+    /// This method can be called either by the Tenderbox foundation or by an appointed verifier
+    /// whose scope covers one of the supplier's industries and one of its regions.
+    pub fn verify_supplier(&mut self, supplier_account_id: AccountId) -> bool {
+        let supplier = self
+            .suppliers
+            .get(&supplier_account_id)
+            .unwrap_or_else(|| Error::SupplierNotRegistered.panic());
+        let verifier_account_id = env::predecessor_account_id();
+        if !self.foundation.is(&verifier_account_id) {
+            let scope = self
+                .verifiers
+                .get(&verifier_account_id)
+                .unwrap_or_else(|| Error::NotFoundationOrVerifier.panic());
+            require!(
+                supplier.profile.industries.iter().any(|industry| scope.industries.contains(industry))
+                    && supplier.profile.regions.iter().any(|region| scope.regions.contains(region)),
+                Error::SupplierOutOfScope
+            );
+        }
+        let was_verified = self.get_supplier_verifier(supplier_account_id.clone()).is_some();
+        self.verified_suppliers
+            .insert(&supplier_account_id, &verifier_account_id);
+        self.events.emit(
+            format!(
+                "The supplier @{} was verified by @{}",
+                supplier_account_id, verifier_account_id
+            )
+            .as_bytes(),
+        );
+        !was_verified
+    }
+
+    /// Revokes the verification of the given supplier.
+    /// Returns `true` if the supplier was verified before, `false` otherwise.
+    /// This method can be called either by the Tenderbox foundation or by the verifier that verified the supplier.
+    pub fn revoke_supplier_verification(&mut self, supplier_account_id: AccountId) -> bool {
+        let verifier_account_id = match self.verified_suppliers.get(&supplier_account_id) {
+            Some(verifier_account_id) => verifier_account_id,
+            None => return false,
+        };
+        if env::predecessor_account_id() != verifier_account_id {
+            self.assert_called_by_foundation();
+        }
+        self.verified_suppliers.remove(&supplier_account_id);
+        self.events.emit(
+            format!(
+                "The verification of the supplier @{} was revoked by @{}",
+                supplier_account_id,
+                env::predecessor_account_id()
+            )
+            .as_bytes(),
+        );
+        true
+    }
+
+    /**************/
+    /* Tenderbox Foundation */
+    /**************/
+
+    /// Proposes the given account ID, e.g. a Sputnik DAO, as the new Tenderbox foundation account.
+    /// The role is only handed over once the proposed account calls `accept_foundation_role`,
+    /// which a DAO does through a function-call proposal.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn propose_foundation_account_id(&mut self, foundation_account_id: AccountId) {
+        self.assert_called_by_foundation();
+        self.foundation.propose(foundation_account_id);
+    }
+
+    /// Accepts the Tenderbox foundation role proposed to the predecessor.
+    /// This method can only be called by the account proposed with `propose_foundation_account_id`.
+    pub fn accept_foundation_role(&mut self) {
+        self.foundation.accept();
+        self.events.emit(
+            format!(
+                "The Tenderbox foundation role was handed over to @{}",
+                self.foundation.account_id()
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Appoints the given account ID as a verifier organization within the given scope, or changes its scope.
+    /// Returns `true` if the verifier was not appointed before, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn add_verifier(&mut self, verifier_account_id: AccountId, scope: VerifierScope) -> bool {
+        self.assert_called_by_foundation();
+        require!(env::is_valid_account_id(verifier_account_id.as_bytes()), Error::InvalidAccountId);
+        self.events.emit(format!("The verifier @{} was appointed", verifier_account_id).as_bytes());
+        self.verifiers.insert(&verifier_account_id, &scope).is_none()
+    }
+
+    /// Blacklists the given account ID with the given justification until the given block timestamp in nanoseconds.
+    /// Replaces an existing entry, so it can also shorten or extend a blacklisting.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn blacklist_supplier(&mut self, supplier_account_id: AccountId, reason: String, until: U64) {
+        self.assert_called_by_foundation();
+        require!(env::is_valid_account_id(supplier_account_id.as_bytes()), Error::InvalidAccountId);
+        require!(!reason.is_empty(), Error::BlacklistingNotJustified);
+        require!(until.0 > env::block_timestamp(), Error::BlacklistingInPast);
+        self.events.emit(
+            format!(
+                "The supplier @{} is blacklisted until {}: {}",
+                supplier_account_id, until.0, reason
+            )
+            .as_bytes(),
+        );
+        self.blacklist
+            .insert(&supplier_account_id, &BlacklistEntry { reason, until });
+    }
+
+    /// Sets the policy suspending suppliers after repeated adverse rulings, or disables it with `None`.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn set_suspension_policy(&mut self, suspension_policy: Option<SuspensionPolicy>) {
+        self.assert_called_by_foundation();
+        if let Some(policy) = suspension_policy.as_ref() {
+            require!(
+                policy.max_disputes_lost > 0 && policy.period.0 > 0,
+                Error::InvalidSuspensionPolicy
+            );
+        }
+        self.suspension_policy = suspension_policy;
+        self.events.emit(b"The suspension policy changed");
+    }
+
+    /// Clears the blacklist entry of the given account ID.
+    /// Returns `true` if the account had an entry, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn remove_from_blacklist(&mut self, supplier_account_id: AccountId) -> bool {
+        self.assert_called_by_foundation();
+        self.events.emit(
+            format!("The supplier @{} was removed from the blacklist", supplier_account_id).as_bytes(),
+        );
+        self.blacklist.remove(&supplier_account_id).is_some()
+    }
+
+    /// Announces a new bond suppliers have to lock, in effect after the timelock delay.
+    /// Registered suppliers below the new amount have to top up to continue bidding.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn set_supplier_bond(&mut self, supplier_bond: U128) {
+        self.assert_called_by_foundation();
+        let effective_at = self
+            .supplier_bond
+            .schedule(supplier_bond.0, self.timelock_delay.get());
+        self.events.emit(
+            format!(
+                "The supplier bond changes to {} at {}",
+                supplier_bond.0, effective_at
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Announces a new timelock delay in nanoseconds, in effect after the current timelock delay.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn set_timelock_delay(&mut self, timelock_delay: U64) {
+        self.assert_called_by_foundation();
+        let effective_at = self
+            .timelock_delay
+            .schedule(timelock_delay.0, self.timelock_delay.get());
+        self.events.emit(
+            format!(
+                "The timelock delay changes to {} at {}",
+                timelock_delay.0, effective_at
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Cancels the configuration changes that are not in effect yet.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn cancel_pending_config(&mut self) {
+        self.assert_called_by_foundation();
+        self.supplier_bond.cancel();
+        self.timelock_delay.cancel();
+        self.events.emit(b"The pending configuration changes are cancelled");
+    }
+
+    /// Slashes the given amount from the bond of the given supplier, including a bond in the unbonding period,
+    /// and sends it to the given beneficiary, e.g. the issuer harmed according to the arbitration outcome.
+    /// Verified tenders slash through `report_slash` instead.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn slash_bond(
+        &mut self,
+        supplier_account_id: AccountId,
+        amount: U128,
+        beneficiary_account_id: AccountId,
+    ) -> Promise {
+        self.assert_called_by_foundation();
+        require!(
+            env::is_valid_account_id(beneficiary_account_id.as_bytes()),
+            Error::InvalidBeneficiaryAccountId
+        );
+        self.internal_slash_bond(&supplier_account_id, amount.0, beneficiary_account_id)
+    }
+
+    /// Removes the given verifier organization. The suppliers it verified are no longer verified.
+    /// Returns `true` if the verifier was appointed before, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn remove_verifier(&mut self, verifier_account_id: AccountId) -> bool {
+        self.assert_called_by_foundation();
+        self.events.emit(format!("The verifier @{} was removed", verifier_account_id).as_bytes());
+        self.verifiers.remove(&verifier_account_id).is_some()
+    }
+
+    /// Adds the given category code with its name to the taxonomy, or renames it.
+    /// Returns `true` if the category was not part of the taxonomy before, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn add_category(&mut self, code: u32, name: String) -> bool {
+        self.assert_called_by_foundation();
+        self.categories.insert(&code, &name).is_none()
+    }
+
+    /************/
+    /* Internal */
+    /************/
+
+    /// Internal method to verify the predecessor was the Tenderbox Foundation account ID.
+    fn assert_called_by_foundation(&self) {
+        self.foundation.assert_called_by();
+    }
+
+    /// Internal method slashing the given amount from the bond of the given supplier, including a bond in the
+    /// unbonding period, and sending it to the given beneficiary.
+    fn internal_slash_bond(
+        &mut self,
+        supplier_account_id: &AccountId,
+        amount: Balance,
+        beneficiary_account_id: AccountId,
+    ) -> Promise {
+        if let Some(mut supplier) = self.suppliers.get(supplier_account_id) {
+            require!(supplier.bond >= amount, Error::BondTooSmall);
+            supplier.bond -= amount;
+            self.suppliers.insert(supplier_account_id, &supplier);
+        } else {
+            let mut unbonding = self
+                .unbonding
+                .get(supplier_account_id)
+                .unwrap_or_else(|| Error::NoBond.panic());
+            require!(unbonding.amount >= amount, Error::BondTooSmall);
+            unbonding.amount -= amount;
+            self.unbonding.insert(supplier_account_id, &unbonding);
+        }
+        self.events.emit(
+            format!(
+                "Slashed {} from the bond of the supplier @{} to @{}",
+                amount, supplier_account_id, beneficiary_account_id
+            )
+            .as_bytes(),
+        );
+        Promise::new(beneficiary_account_id).transfer(amount)
+    }
+
+    /// Internal method returning the registered suppliers from `from_index` to `from_index + limit` matching the filter.
+    fn internal_get_suppliers<F: Fn(&SupplierView) -> bool>(
+        &self,
+        from_index: u64,
+        limit: u64,
+        filter: F,
+    ) -> Vec<SupplierView> {
+        let keys = self.suppliers.keys_as_vector();
+        let values = self.suppliers.values_as_vector();
+        (from_index..std::cmp::min(from_index + limit, keys.len()))
+            .map(|index| {
+                let account_id = keys.get(index).unwrap();
+                SupplierView {
+                    profile: values.get(index).unwrap().profile,
+                    verified: self.is_supplier_verified(account_id.clone()),
+                    blacklisted: self.is_blacklisted(account_id.clone()),
+                    account_id,
+                }
+            })
+            .filter(|supplier| filter(supplier))
+            .collect()
+    }
+
+    /// Internal method to add the given supplier to the index of the given category code.
+    fn internal_index_category(&mut self, code: u32, supplier_account_id: &AccountId) {
+        let mut suppliers = self
+            .suppliers_by_category
+            .get(&code)
+            .unwrap_or_else(|| UnorderedSet::new(StorageKey::SuppliersInCategory { code }));
+        suppliers.insert(supplier_account_id);
+        self.suppliers_by_category.insert(&code, &suppliers);
+    }
+
+    /// Internal method blacklisting the given supplier for the suspension period once it lost as many
+    /// disputes as the suspension policy allows. An active blacklisting is only extended, keeping its reason.
+    fn internal_apply_suspension_policy(&mut self, supplier_account_id: &AccountId, disputes_lost: u64) {
+        let policy = match self.suspension_policy.as_ref() {
+            Some(policy) if disputes_lost >= policy.max_disputes_lost => policy,
+            _ => return,
+        };
+        let until = env::block_timestamp().saturating_add(policy.period.0);
+        let entry = match self.blacklist.get(supplier_account_id) {
+            Some(entry) if entry.until.0 >= until => return,
+            Some(entry) if entry.until.0 > env::block_timestamp() => BlacklistEntry {
+                reason: entry.reason,
+                until: until.into(),
+            },
+            _ => BlacklistEntry {
+                reason: format!("Lost {} disputes", disputes_lost),
+                until: until.into(),
+            },
+        };
+        self.events.emit(
+            format!(
+                "The supplier @{} is suspended until {} after losing {} disputes",
+                supplier_account_id, until, disputes_lost
+            )
+            .as_bytes(),
+        );
+        self.blacklist.insert(supplier_account_id, &entry);
+    }
+
+    /// Internal method to remove the given supplier record and its category index entries.
+    fn internal_remove_supplier(&mut self, supplier_account_id: &AccountId) -> Option<Supplier> {
+        let supplier = self.suppliers.remove(supplier_account_id)?;
+        self.verified_suppliers.remove(supplier_account_id);
+        for code in supplier.profile.categories.iter() {
+            if let Some(mut suppliers) = self.suppliers_by_category.get(code) {
+                suppliers.remove(supplier_account_id);
+                if suppliers.is_empty() {
+                    self.suppliers_by_category.remove(code);
+                } else {
+                    self.suppliers_by_category.insert(code, &suppliers);
+                }
+            }
+        }
+        Some(supplier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::{testing_env, MockedBlockchain, PromiseResult};
+
+    mod test_utils;
+    use proptest::prelude::*;
+    use test_utils::*;
+
+    fn supplier_profile() -> SupplierProfile {
+        SupplierProfile {
+            company_name_hash: env::sha256(b"Acme Supplies Ltd").into(),
+            industries: vec!["Agriculture".to_string()],
+            regions: vec!["Nairobi".to_string()],
+            categories: vec![],
+            capacity: 1_000,
+        }
+    }
+
+    #[test]
+    fn test_register_supplier() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+
+        // Check the supplier is not registered
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(!contract.is_registered(account_supplier()));
+
+        // Registering the supplier
+        context.is_view = false;
+        context.predecessor_account_id = account_supplier();
+        context.attached_deposit = ntoy(11);
+        testing_env!(context.clone());
+        assert!(contract.register_supplier(supplier_profile()));
+
+        // Updating the profile, the previous storage deposit covers it
+        context.attached_deposit = 0;
+        testing_env!(context.clone());
+        let mut profile = supplier_profile();
+        profile.capacity = 2_000;
+        assert!(!contract.register_supplier(profile));
+
+        // Checking the profile
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.is_registered(account_supplier()));
+        assert_eq!(contract.get_supplier(account_supplier()).unwrap().capacity, 2_000);
+        assert_eq!(contract.get_number_of_suppliers(), 1);
+
+        // Unregistering
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.unregister_supplier());
+        assert!(!contract.unregister_supplier());
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(!contract.is_registered(account_supplier()));
+    }
+
+    #[test]
+    #[should_panic(expected = "E602: Not enough attached deposit to cover the storage of the supplier profile")]
+    fn test_register_supplier_not_enough_deposit() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_supplier())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+        context.attached_deposit = 0;
+        testing_env!(context.clone());
+        contract.register_supplier(supplier_profile());
+    }
+
+    #[test]
+    fn test_suppliers_by_category() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+
+        // Adding categories to the taxonomy by the foundation
+        assert!(contract.add_category(50_000_000, "Food Beverage and Tobacco Products".to_string()));
+        assert!(contract.add_category(43_000_000, "Information Technology".to_string()));
+        assert!(!contract.add_category(43_000_000, "IT Broadcasting and Telecommunications".to_string()));
+
+        // Registering the supplier in a category
+        context.predecessor_account_id = account_supplier();
+        context.attached_deposit = ntoy(11);
+        testing_env!(context.clone());
+        let mut profile = supplier_profile();
+        profile.categories = vec![50_000_000];
+        contract.register_supplier(profile);
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_categories(0, 10).len(), 2);
+        assert_eq!(
+            contract.get_suppliers_by_category(50_000_000, 0, 10),
+            vec![account_supplier()]
+        );
+        assert!(contract.get_suppliers_by_category(43_000_000, 0, 10).is_empty());
+
+        // Moving the supplier to the other category
+        context.is_view = false;
+        context.attached_deposit = 0;
+        testing_env!(context.clone());
+        let mut profile = supplier_profile();
+        profile.categories = vec![43_000_000];
+        contract.register_supplier(profile);
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.get_suppliers_by_category(50_000_000, 0, 10).is_empty());
+        assert_eq!(
+            contract.get_suppliers_by_category(43_000_000, 0, 10),
+            vec![account_supplier()]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "E411: Unknown category code 50000000")]
+    fn test_register_supplier_unknown_category() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_supplier())
+            .finish();
+        context.attached_deposit = ntoy(11);
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+        let mut profile = supplier_profile();
+        profile.categories = vec![50_000_000];
+        contract.register_supplier(profile);
+    }
+
+    #[test]
+    fn test_delegated_supplier_verification() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+
+        // Appointing the verifier by the foundation
+        assert!(contract.add_verifier(
+            account_verifier(),
+            VerifierScope {
+                industries: vec!["Agriculture".to_string()],
+                regions: vec!["Nairobi".to_string()],
+            }
+        ));
+
+        // Registering the supplier
+        context.predecessor_account_id = account_supplier();
+        context.attached_deposit = ntoy(11);
+        testing_env!(context.clone());
+        contract.register_supplier(supplier_profile());
+
+        // Verifying the supplier by the verifier
+        context.predecessor_account_id = account_verifier();
+        context.attached_deposit = 0;
+        testing_env!(context.clone());
+        assert!(contract.verify_supplier(account_supplier()));
+        assert!(!contract.verify_supplier(account_supplier()));
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.is_supplier_verified(account_supplier()));
+        assert_eq!(
+            contract.get_supplier_verifier(account_supplier()),
+            Some(account_verifier())
+        );
+
+        // Removing the verifier invalidates its verifications
+        context.is_view = false;
+        context.predecessor_account_id = account_tenderbox();
+        testing_env!(context.clone());
+        assert!(contract.remove_verifier(account_verifier()));
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(!contract.is_supplier_verified(account_supplier()));
+
+        // Verifying by the foundation and revoking it
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.verify_supplier(account_supplier()));
+        assert!(contract.revoke_supplier_verification(account_supplier()));
+        assert!(!contract.revoke_supplier_verification(account_supplier()));
+    }
+
+    #[test]
+    fn test_verified_capacity() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_supplier())
+            .finish();
+        context.attached_deposit = ntoy(11);
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+        contract.register_supplier(supplier_profile());
+
+        // The declared capacity only counts once verified
+        context.attached_deposit = 0;
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_verified_capacity(account_supplier()), None);
+        assert!(!contract.has_capacity(account_supplier(), 500));
+
+        context.is_view = false;
+        context.predecessor_account_id = account_tenderbox();
+        testing_env!(context.clone());
+        contract.verify_supplier(account_supplier());
+        assert_eq!(contract.get_verified_capacity(account_supplier()), Some(1_000));
+        assert!(contract.has_capacity(account_supplier(), 500));
+        assert!(!contract.has_capacity(account_supplier(), 1_001));
+
+        // Raising the declared capacity requires a new verification
+        context.predecessor_account_id = account_supplier();
+        testing_env!(context.clone());
+        let mut profile = supplier_profile();
+        profile.capacity = 5_000;
+        contract.register_supplier(profile);
+        assert!(!contract.has_capacity(account_supplier(), 500));
+    }
+
+    #[test]
+    #[should_panic(expected = "E522: The supplier is outside of the verifier's scope")]
+    fn test_verify_supplier_outside_of_scope() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+        contract.add_verifier(
+            account_verifier(),
+            VerifierScope {
+                industries: vec!["Construction".to_string()],
+                regions: vec!["Nairobi".to_string()],
+            },
+        );
+
+        context.predecessor_account_id = account_supplier();
+        context.attached_deposit = ntoy(11);
+        testing_env!(context.clone());
+        contract.register_supplier(supplier_profile());
+
+        context.predecessor_account_id = account_verifier();
+        context.attached_deposit = 0;
+        testing_env!(context.clone());
+        contract.verify_supplier(account_supplier());
+    }
+
+    #[test]
+    fn test_blacklist_supplier() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        context.block_timestamp = 1_000;
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+
+        // Blacklisting the supplier by the foundation
+        contract.blacklist_supplier(account_supplier(), "Bid rigging".to_string(), 2_000.into());
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.is_blacklisted(account_supplier()));
+        assert_eq!(
+            contract.get_blacklist_entry(account_supplier()).unwrap().reason,
+            "Bid rigging"
+        );
+
+        // The blacklisting has expired
+        context.block_timestamp = 2_000;
+        testing_env!(context.clone());
+        assert!(!contract.is_blacklisted(account_supplier()));
+
+        // Clearing the entry
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.remove_from_blacklist(account_supplier()));
+        assert!(contract.get_blacklist_entry(account_supplier()).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "E101: Can only be called by the Tenderbox Foundation")]
+    fn test_blacklist_supplier_not_by_foundation() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_verifier())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+        contract.blacklist_supplier(account_supplier(), "Bid rigging".to_string(), 2_000.into());
+    }
+
+    #[test]
+    fn test_reputation_report() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+
+        // The tender reports the award, which is authenticated by the verify tender contract
+        context.predecessor_account_id = account_tender();
+        testing_env!(context.clone());
+        contract.report_reputation(account_supplier(), ReputationReport::TenderWon);
+
+        context.predecessor_account_id = account_supplier_registry();
+        testing_env_with_promise_results(
+            context.clone(),
+            PromiseResult::Successful(b"true".to_vec()),
+        );
+        assert!(contract.on_report_authenticated(
+            account_tender(),
+            account_supplier(),
+            ReputationReport::TenderWon
+        ));
+        assert!(contract.on_report_authenticated(
+            account_tender(),
+            account_supplier(),
+            ReputationReport::TenderCompleted { on_time: true, value: ntoy(100).into() }
+        ));
+
+        // A report from an account that isn't a verified tender is ignored
+        testing_env_with_promise_results(
+            context.clone(),
+            PromiseResult::Successful(b"false".to_vec()),
+        );
+        assert!(!contract.on_report_authenticated(
+            account_tender_owner(),
+            account_supplier(),
+            ReputationReport::DisputeLost { penalty_points: 10 }
+        ));
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        let reputation = contract.get_reputation(account_supplier());
+        assert_eq!(reputation.tenders_won, 1);
+        assert_eq!(reputation.completed_on_time, 1);
+        assert_eq!(reputation.disputes_lost, 0);
+        assert_eq!(reputation.total_value_delivered.0, ntoy(100));
+    }
+
+    #[test]
+    fn test_suspension_after_disputes_lost() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+        contract.set_suspension_policy(Some(SuspensionPolicy {
+            max_disputes_lost: 2,
+            period: 1_000.into(),
+        }));
+
+        // The second adverse ruling suspends the supplier
+        context.predecessor_account_id = account_supplier_registry();
+        testing_env_with_promise_results(
+            context.clone(),
+            PromiseResult::Successful(b"true".to_vec()),
+        );
+        contract.on_report_authenticated(
+            account_tender(),
+            account_supplier(),
+            ReputationReport::DisputeLost { penalty_points: 10 },
+        );
+        assert!(!contract.is_blacklisted(account_supplier()));
+        contract.on_report_authenticated(
+            account_tender(),
+            account_supplier(),
+            ReputationReport::DisputeLost { penalty_points: 25 },
+        );
+        assert!(contract.is_blacklisted(account_supplier()));
+        assert_eq!(contract.get_reputation(account_supplier()).penalty_points, 35);
+
+        // The suspension lifts after its period
+        context.block_timestamp += 1_000;
+        testing_env!(context.clone());
+        assert!(!contract.is_blacklisted(account_supplier()));
+    }
+
+    #[test]
+    fn test_suspension_keeps_foundation_blacklisting() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+        contract.set_suspension_policy(Some(SuspensionPolicy {
+            max_disputes_lost: 1,
+            period: 1_000.into(),
+        }));
+        contract.blacklist_supplier(account_supplier(), "Fraudulent invoices".to_string(), 5_000.into());
+
+        // A shorter suspension leaves the blacklisting of the foundation untouched
+        context.predecessor_account_id = account_supplier_registry();
+        testing_env_with_promise_results(
+            context.clone(),
+            PromiseResult::Successful(b"true".to_vec()),
+        );
+        contract.on_report_authenticated(
+            account_tender(),
+            account_supplier(),
+            ReputationReport::DisputeLost { penalty_points: 10 },
+        );
+        let entry = contract.get_blacklist_entry(account_supplier()).unwrap();
+        assert_eq!(entry.until.0, 5_000);
+        assert_eq!(entry.reason, "Fraudulent invoices");
+
+        // A longer one extends it, keeping the reason of the foundation
+        context.block_timestamp = 4_500;
+        testing_env_with_promise_results(
+            context.clone(),
+            PromiseResult::Successful(b"true".to_vec()),
+        );
+        contract.on_report_authenticated(
+            account_tender(),
+            account_supplier(),
+            ReputationReport::DisputeLost { penalty_points: 10 },
+        );
+        let entry = contract.get_blacklist_entry(account_supplier()).unwrap();
+        assert_eq!(entry.until.0, 5_500);
+        assert_eq!(entry.reason, "Fraudulent invoices");
+    }
+
+    #[test]
+    #[should_panic(expected = "E103: Can only be called by the contract itself")]
+    fn test_reputation_callback_not_by_self() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_tender())
+            .finish();
+        testing_env_with_promise_results(
+            context.clone(),
+            PromiseResult::Successful(b"true".to_vec()),
+        );
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+        contract.on_report_authenticated(account_tender(), account_supplier(), ReputationReport::TenderWon);
+    }
+
+    #[test]
+    fn test_supplier_bond() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_supplier())
+            .finish();
+        context.attached_deposit = ntoy(11);
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+        contract.register_supplier(supplier_profile());
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_bond(account_supplier()).0, DEFAULT_SUPPLIER_BOND);
+        assert!(contract.has_sufficient_bond(account_supplier()));
+
+        // Slashing part of the bond by the foundation
+        context.is_view = false;
+        context.attached_deposit = 0;
+        context.predecessor_account_id = account_tenderbox();
+        testing_env!(context.clone());
+        contract.slash_bond(account_supplier(), ntoy(4).into(), account_tender_owner());
+        assert!(!contract.has_sufficient_bond(account_supplier()));
+
+        // Topping up to continue bidding
+        context.predecessor_account_id = account_supplier();
+        context.attached_deposit = ntoy(4);
+        testing_env!(context.clone());
+        assert_eq!(contract.top_up_bond().0, DEFAULT_SUPPLIER_BOND);
+        assert!(contract.has_sufficient_bond(account_supplier()));
+
+        // Unregistering keeps the bond locked for the unbonding period
+        context.attached_deposit = 0;
+        testing_env!(context.clone());
+        assert!(contract.unregister_supplier());
+        assert_eq!(contract.get_bond(account_supplier()).0, 0);
+
+        context.block_timestamp += UNBONDING_PERIOD;
+        testing_env!(context.clone());
+        contract.withdraw_bond();
+    }
+
+    #[test]
+    fn test_slash_by_tender() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_supplier())
+            .finish();
+        context.attached_deposit = ntoy(11);
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+        contract.register_supplier(supplier_profile());
+
+        // The tender slashes the bond, which is authenticated by the verify tender contract
+        context.attached_deposit = 0;
+        context.predecessor_account_id = account_tender();
+        testing_env!(context.clone());
+        contract.report_slash(account_supplier(), ntoy(4).into(), account_tender_owner());
+
+        context.predecessor_account_id = account_supplier_registry();
+        testing_env_with_promise_results(
+            context.clone(),
+            PromiseResult::Successful(b"true".to_vec()),
+        );
+        assert!(contract.on_slash_authenticated(
+            account_tender(),
+            account_supplier(),
+            ntoy(4).into(),
+            account_tender_owner()
+        ));
+        assert_eq!(contract.get_bond(account_supplier()).0, DEFAULT_SUPPLIER_BOND - ntoy(4));
+
+        // A slash from an account that isn't a verified tender is ignored
+        testing_env_with_promise_results(
+            context.clone(),
+            PromiseResult::Successful(b"false".to_vec()),
+        );
+        assert!(!contract.on_slash_authenticated(
+            account_tender_owner(),
+            account_supplier(),
+            ntoy(4).into(),
+            account_tender_owner()
+        ));
+        assert_eq!(contract.get_bond(account_supplier()).0, DEFAULT_SUPPLIER_BOND - ntoy(4));
+    }
+
+    #[test]
+    #[should_panic(expected = "E516: The bond is still in the unbonding period")]
+    fn test_withdraw_bond_too_early() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_supplier())
+            .finish();
+        context.attached_deposit = ntoy(11);
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+        contract.register_supplier(supplier_profile());
+
+        context.attached_deposit = 0;
+        testing_env!(context.clone());
+        contract.unregister_supplier();
+        contract.withdraw_bond();
+    }
+
+    #[test]
+    #[should_panic(expected = "E603: Not enough attached deposit to lock the registration bond")]
+    fn test_register_supplier_without_bond() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_supplier())
+            .finish();
+        context.attached_deposit = ntoy(1);
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+        contract.register_supplier(supplier_profile());
+    }
+
+    #[test]
+    fn test_supplier_discovery() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_supplier())
+            .finish();
+        context.attached_deposit = ntoy(11);
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+        contract.register_supplier(supplier_profile());
+
+        context.predecessor_account_id = account_tender_owner();
+        testing_env!(context.clone());
+        let mut profile = supplier_profile();
+        profile.industries = vec!["Construction".to_string()];
+        profile.regions = vec!["Mombasa".to_string()];
+        contract.register_supplier(profile);
+
+        // Verifying the first supplier by the foundation
+        context.predecessor_account_id = account_tenderbox();
+        context.attached_deposit = 0;
+        testing_env!(context.clone());
+        contract.verify_supplier(account_supplier());
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_suppliers(0, 10).len(), 2);
+        assert_eq!(contract.get_suppliers(1, 10).len(), 1);
+        let verified = contract.get_verified_suppliers(0, 10);
+        assert_eq!(verified.len(), 1);
+        assert_eq!(verified[0].account_id, account_supplier());
+        assert_eq!(
+            contract.get_suppliers_by_industry("Construction".to_string(), 0, 10)[0].account_id,
+            account_tender_owner()
+        );
+        assert!(contract
+            .get_suppliers_by_region("Kisumu".to_string(), 0, 10)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_endorsements() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+        contract.add_category(50_000_000, "Food Beverage and Tobacco Products".to_string());
+
+        // Registering two suppliers
+        context.attached_deposit = ntoy(11);
+        context.predecessor_account_id = account_supplier();
+        testing_env!(context.clone());
+        let mut profile = supplier_profile();
+        profile.categories = vec![50_000_000];
+        contract.register_supplier(profile);
+
+        context.predecessor_account_id = account_tender_owner();
+        testing_env!(context.clone());
+        contract.register_supplier(supplier_profile());
+
+        // Endorsing the first supplier
+        context.attached_deposit = 0;
+        testing_env!(context.clone());
+        assert!(contract.endorse(account_supplier(), 50_000_000));
+        assert!(!contract.endorse(account_supplier(), 50_000_000));
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_endorsement_count(account_supplier(), 50_000_000), 1);
+        assert!(contract.has_endorsed(account_tender_owner(), account_supplier(), 50_000_000));
+
+        // Revoking the endorsement
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.revoke_endorsement(account_supplier(), 50_000_000));
+        assert!(!contract.revoke_endorsement(account_supplier(), 50_000_000));
+        assert_eq!(contract.get_endorsement_count(account_supplier(), 50_000_000), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "E517: Only registered suppliers can endorse")]
+    fn test_endorse_by_unregistered_account() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_supplier_registry())
+            .predecessor_account_id(account_tender_owner())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+        contract.endorse(account_supplier(), 50_000_000);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_unbonding_boundary(unregistered_at in 0..u64::MAX / 2, elapsed in 0..UNBONDING_PERIOD * 2) {
+            let unbonding = UnbondingBond {
+                amount: ntoy(10),
+                release_at: unregistered_at + UNBONDING_PERIOD,
+            };
+            prop_assert_eq!(
+                unbonding.is_released_at(unregistered_at + elapsed),
+                elapsed >= UNBONDING_PERIOD
+            );
+        }
+
+        #[test]
+        fn prop_slash_bond(bond in 1..=ntoy(1_000), slashed in 0..=ntoy(1_000)) {
+            let context = VMContextBuilder::new()
+                .current_account_id(account_supplier_registry())
+                .predecessor_account_id(account_tenderbox())
+                .finish();
+            testing_env!(context);
+            let mut contract = SupplierRegistry::new(account_tenderbox(), account_verify_tender());
+            contract.unbonding.insert(
+                &account_supplier(),
+                &UnbondingBond {
+                    amount: bond,
+                    release_at: UNBONDING_PERIOD,
+                },
+            );
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                contract.slash_bond(account_supplier(), slashed.into(), account_tenderbox());
+            }));
+            prop_assert_eq!(result.is_ok(), slashed <= bond);
+            if slashed <= bond {
+                prop_assert_eq!(contract.unbonding.get(&account_supplier()).unwrap().amount, bond - slashed);
+            }
+        }
+
+        #[test]
+        fn prop_penalty_points_saturate(
+            penalty_points_before in 0..=u64::MAX,
+            penalty_points in 0..=u32::MAX,
+        ) {
+            let context = VMContextBuilder::new()
+                .current_account_id(account_supplier_registry())
+                .predecessor_account_id(account_supplier_registry())
+                .finish();
+            testing_env_with_promise_results(context, PromiseResult::Successful(b"true".to_vec()));
+            let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+            contract.reputations.insert(
+                &account_supplier(),
+                &Reputation {
+                    penalty_points: penalty_points_before,
+                    ..Reputation::default()
+                },
+            );
+            contract.on_report_authenticated(
+                account_tender(),
+                account_supplier(),
+                ReputationReport::DisputeLost { penalty_points },
+            );
+            let reputation = contract.get_reputation(account_supplier());
+            prop_assert_eq!(reputation.disputes_lost, 1);
+            prop_assert_eq!(
+                reputation.penalty_points,
+                penalty_points_before.saturating_add(penalty_points as u64)
+            );
+        }
+
+        #[test]
+        fn prop_blacklist_boundary(until in 1..u64::MAX - 1, offset in 0u64..=2) {
+            let mut context = VMContextBuilder::new()
+                .current_account_id(account_supplier_registry())
+                .predecessor_account_id(account_tenderbox())
+                .finish();
+            testing_env!(context.clone());
+            let mut contract = SupplierRegistry::new(account_tenderbox(), account_verified());
+            contract.blacklist.insert(
+                &account_supplier(),
+                &BlacklistEntry {
+                    reason: "Fraud".to_string(),
+                    until: until.into(),
+                },
+            );
+
+            // One nanosecond before, at and after the end of the blacklisting
+            context.block_timestamp = until - 1 + offset;
+            testing_env!(context);
+            prop_assert_eq!(contract.is_blacklisted(account_supplier()), offset == 0);
+        }
+    }
+}
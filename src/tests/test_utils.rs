@@ -0,0 +1,220 @@
+#![allow(dead_code)]
+
+use near_sdk::{AccountId, Balance, MockedBlockchain, PromiseResult, VMContext};
+
+// Testing helpers shared by the unit tests of all the Tenderbox contracts. Every contract declares
+// `mod test_utils;` inside its `tests` module, which resolves to this file.
+
+/**************/
+/* Accounts */
+/**************/
+
+pub fn account_tenderbox() -> AccountId {
+    "tenderbox".to_string()
+}
+
+pub fn account_factory() -> AccountId {
+    "factory".to_string()
+}
+
+pub fn account_tenderfactory() -> AccountId {
+    "tenderfactory".to_string()
+}
+
+pub fn account_verify_tender() -> AccountId {
+    "verify-tender".to_string()
+}
+
+pub fn account_verified() -> AccountId {
+    "verified".to_string()
+}
+
+/// The registration ID of the sample tender, see `account_tender`.
+pub fn tender_registration_id() -> String {
+    "tender".to_string()
+}
+
+/// The account ID of the sample tender issued by `account_factory`.
+pub fn account_tender() -> AccountId {
+    format!("{}.{}", tender_registration_id(), account_factory())
+}
+
+pub fn account_tender_owner() -> AccountId {
+    "tender-owner".to_string()
+}
+
+pub fn account_tokens_owner() -> AccountId {
+    "tokens-owner".to_string()
+}
+
+pub fn account_supplier() -> AccountId {
+    "supplier".to_string()
+}
+
+pub fn account_attester() -> AccountId {
+    "attester".to_string()
+}
+
+pub fn account_verifier() -> AccountId {
+    "verifier".to_string()
+}
+
+pub fn account_dao() -> AccountId {
+    "dao".to_string()
+}
+
+pub fn account_operator() -> AccountId {
+    "operator".to_string()
+}
+
+pub fn account_council_member(index: usize) -> AccountId {
+    format!("council-member-{}", index)
+}
+
+pub fn account_supplier_registry() -> AccountId {
+    "supplier-registry".to_string()
+}
+
+pub fn account_treasury() -> AccountId {
+    "treasury".to_string()
+}
+
+pub fn account_parameter_registry() -> AccountId {
+    "parameter-registry".to_string()
+}
+
+pub fn account_upgrade_coordinator() -> AccountId {
+    "upgrade-coordinator".to_string()
+}
+
+pub fn account_certificates() -> AccountId {
+    "certificates".to_string()
+}
+
+pub fn account_stablecoin() -> AccountId {
+    "stablecoin".to_string()
+}
+
+pub fn account_legacy_whitelist() -> AccountId {
+    "legacy-whitelist".to_string()
+}
+
+pub fn account_arbitrator_registry() -> AccountId {
+    "arbitrator-registry".to_string()
+}
+
+pub fn account_arbitrator(index: usize) -> AccountId {
+    format!("arbitrator-{}", index)
+}
+
+pub fn account_sponsor() -> AccountId {
+    "sponsor".to_string()
+}
+
+/**************/
+/* Deposits */
+/**************/
+
+/// Converts the given amount of NEAR to yoctoNEAR.
+pub fn ntoy(near_amount: Balance) -> Balance {
+    near_amount * 10u128.pow(24)
+}
+
+/**************/
+/* Context */
+/**************/
+
+/// Replaces the mocked blockchain by one with the given context and promise result, keeping the
+/// storage of the previous one so the contract state survives.
+pub fn testing_env_with_promise_results(context: VMContext, promise_result: PromiseResult) {
+    let storage = match near_sdk::env::take_blockchain_interface() {
+        Some(mut blockchain) => blockchain
+            .as_mut_mocked_blockchain()
+            .unwrap()
+            .take_storage(),
+        None => Default::default(),
+    };
+
+    near_sdk::env::set_blockchain_interface(Box::new(MockedBlockchain::new(
+        context,
+        Default::default(),
+        Default::default(),
+        vec![promise_result],
+        storage,
+        Default::default(),
+        None,
+    )));
+}
+
+/// Builder of the `VMContext` passed to `testing_env!`.
+pub struct VMContextBuilder {
+    context: VMContext,
+}
+
+impl VMContextBuilder {
+    pub fn new() -> Self {
+        Self {
+            context: VMContext {
+                current_account_id: "".to_string(),
+                signer_account_id: "".to_string(),
+                signer_account_pk: vec![0, 1, 2],
+                predecessor_account_id: "".to_string(),
+                input: vec![],
+                block_index: 0,
+                epoch_height: 0,
+                block_timestamp: 0,
+                account_balance: 0,
+                account_locked_balance: 0,
+                storage_usage: 10u64.pow(6),
+                attached_deposit: 0,
+                prepaid_gas: 10u64.pow(18),
+                random_seed: vec![0, 1, 2],
+                is_view: false,
+                output_data_receivers: vec![],
+            },
+        }
+    }
+
+    pub fn current_account_id(mut self, account_id: AccountId) -> Self {
+        self.context.current_account_id = account_id;
+        self
+    }
+
+    pub fn signer_account_id(mut self, account_id: AccountId) -> Self {
+        self.context.signer_account_id = account_id;
+        self
+    }
+
+    /// Sets the predecessor account ID, and the signer account ID unless it was set explicitly.
+    pub fn predecessor_account_id(mut self, account_id: AccountId) -> Self {
+        if self.context.signer_account_id.is_empty() {
+            self.context.signer_account_id = account_id.clone();
+        }
+        self.context.predecessor_account_id = account_id;
+        self
+    }
+
+    pub fn block_timestamp(mut self, timestamp: u64) -> Self {
+        self.context.block_timestamp = timestamp;
+        self
+    }
+
+    pub fn attached_deposit(mut self, amount: Balance) -> Self {
+        self.context.attached_deposit = amount;
+        self
+    }
+
+    pub fn account_balance(mut self, amount: Balance) -> Self {
+        self.context.account_balance = amount;
+        self
+    }
+
+    pub fn is_view(mut self, is_view: bool) -> Self {
+        self.context.is_view = is_view;
+        self
+    }
+
+    pub fn finish(self) -> VMContext {
+        self.context
+    }
+}
@@ -0,0 +1,126 @@
+#![allow(dead_code)]
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::LazyOption;
+use near_sdk::json_types::U64;
+use near_sdk::serde::Serialize;
+use near_sdk::{env, IntoStorageKey};
+
+use crate::errors::*;
+
+// Timelocked configuration values shared by the Tenderbox contracts. A change is announced first
+// and only takes effect once its delay has passed, giving issuers and bidders time to react.
+
+/// The delay configuration changes wait before taking effect, until changed. 2 days.
+pub const DEFAULT_TIMELOCK_DELAY: u64 = 2 * 24 * 60 * 60 * 1_000_000_000;
+
+/// Announced configuration change.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingChange<T> {
+    /// The new value.
+    pub value: T,
+    /// The block timestamp in nanoseconds from which the new value is in effect.
+    pub effective_at: U64,
+}
+
+/// Configuration value whose changes only take effect after a delay.
+/// A matured change is picked up on read, so no transaction is needed to apply it.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Timelocked<T> {
+    current: T,
+    pending: Option<PendingChange<T>>,
+}
+
+impl<T: Clone> Timelocked<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            current: value,
+            pending: None,
+        }
+    }
+
+    /// Returns the value in effect at the current block timestamp.
+    pub fn get(&self) -> T {
+        match &self.pending {
+            Some(change) if change.effective_at.0 <= env::block_timestamp() => change.value.clone(),
+            _ => self.current.clone(),
+        }
+    }
+
+    /// Returns the announced change that is not in effect yet, if any.
+    pub fn pending(&self) -> Option<PendingChange<T>> {
+        self.pending
+            .clone()
+            .filter(|change| change.effective_at.0 > env::block_timestamp())
+    }
+
+    /// Announces the given value to take effect after the given delay in nanoseconds, replacing
+    /// a change that is not in effect yet. Returns the block timestamp from which it is in effect.
+    pub fn schedule(&mut self, value: T, delay: u64) -> u64 {
+        self.current = self.get();
+        let effective_at = env::block_timestamp() + delay;
+        self.pending = Some(PendingChange {
+            value,
+            effective_at: effective_at.into(),
+        });
+        effective_at
+    }
+
+    /// Cancels the announced change that is not in effect yet. Returns `true` if there was one.
+    pub fn cancel(&mut self) -> bool {
+        if self.pending().is_some() {
+            self.pending = None;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Timelocked configuration value stored under its own storage key rather than in the contract
+/// state, so only the calls that use it pay for reading it.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LazyTimelocked<T> {
+    timelocked: LazyOption<Timelocked<T>>,
+}
+
+impl<T: BorshDeserialize + BorshSerialize + Clone> LazyTimelocked<T> {
+    pub fn new<S: IntoStorageKey>(storage_key: S, value: T) -> Self {
+        Self {
+            timelocked: LazyOption::new(storage_key, Some(&Timelocked::new(value))),
+        }
+    }
+
+    /// Returns the value in effect at the current block timestamp.
+    pub fn get(&self) -> T {
+        self.read().get()
+    }
+
+    /// Returns the announced change that is not in effect yet, if any.
+    pub fn pending(&self) -> Option<PendingChange<T>> {
+        self.read().pending()
+    }
+
+    /// Announces the given value, see `Timelocked::schedule`.
+    pub fn schedule(&mut self, value: T, delay: u64) -> u64 {
+        let mut timelocked = self.read();
+        let effective_at = timelocked.schedule(value, delay);
+        self.timelocked.set(&timelocked);
+        effective_at
+    }
+
+    /// Cancels the announced change that is not in effect yet. Returns `true` if there was one.
+    pub fn cancel(&mut self) -> bool {
+        let mut timelocked = self.read();
+        let cancelled = timelocked.cancel();
+        if cancelled {
+            self.timelocked.set(&timelocked);
+        }
+        cancelled
+    }
+
+    fn read(&self) -> Timelocked<T> {
+        self.timelocked.get().unwrap_or_else(|| Error::NotInitialized.panic())
+    }
+}
@@ -0,0 +1,703 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{UnorderedMap, Vector};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, ext_contract, near_bindgen, AccountId, Balance, Promise, PromiseOrValue};
+
+
+// The protocol treasury receives the fees collected across the Tenderbox contracts and pays them
+// out on decision of the Tenderbox foundation.
+
+#[macro_use]
+mod errors;
+use crate::errors::*;
+
+mod foundation;
+use crate::foundation::*;
+
+mod types;
+use crate::types::*;
+
+mod interfaces;
+use crate::interfaces::*;
+
+mod events;
+use crate::events::*;
+
+mod storage;
+use crate::storage::*;
+
+mod utils;
+use crate::utils::*;
+
+
+// The deposit NEP-141 requires to attach to `ft_transfer`.
+const ONE_YOCTO: Balance = 1;
+
+pub mod gas {
+    use near_sdk::Gas;
+
+    pub use crate::interfaces::gas::*;
+
+    /// The amount of Gas the contract will attach to the callback to itself.
+    /// The base for the execution.
+    pub const CALLBACK: Gas = BASE;
+}
+
+/// Amounts received per fee kind and paid out by the treasury.
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct Accounting {
+    pub posting_fees: Balance,
+    pub verification_fees: Balance,
+    pub award_fees: Balance,
+    pub other: Balance,
+    pub disbursed: Balance,
+}
+
+impl Accounting {
+    /// Records the given amount as income of the given fee kind.
+    pub fn record(&mut self, kind: FeeKind, amount: Balance) {
+        let total = match kind {
+            FeeKind::PostingFee => &mut self.posting_fees,
+            FeeKind::VerificationFee => &mut self.verification_fees,
+            FeeKind::AwardFee => &mut self.award_fees,
+            FeeKind::Other => &mut self.other,
+        };
+        *total += amount;
+    }
+
+    /// Returns the income received over all fee kinds.
+    pub fn total_received(&self) -> Balance {
+        self.posting_fees + self.verification_fees + self.award_fees + self.other
+    }
+}
+
+/// Accounting view of the treasury.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AccountingView {
+    pub posting_fees: U128,
+    pub verification_fees: U128,
+    pub award_fees: U128,
+    pub other: U128,
+    pub disbursed: U128,
+    /// The balance that can still be disbursed, excluding the storage of the treasury.
+    pub available: U128,
+}
+
+/// Payment made by the treasury.
+#[derive(BorshDeserialize, BorshSerialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Disbursement {
+    /// The receiver of the payment.
+    pub to: AccountId,
+    /// The amount paid.
+    pub amount: U128,
+    /// The fungible token the payment was made in, or `None` for NEAR.
+    pub token_account_id: Option<AccountId>,
+    /// The purpose of the payment.
+    pub memo: String,
+    /// The block timestamp in nanoseconds of the payment.
+    pub timestamp: U64,
+    /// Whether the payment failed, giving the amount back to the treasury.
+    pub failed: bool,
+}
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Treasury {
+    /// The Tenderbox foundation role. It governs the disbursements.
+    pub foundation: FoundationRole,
+
+    /// The sequence of the events emitted by the contract.
+    pub events: EventLog,
+
+    /// The amounts received per fee kind and paid out.
+    pub accounting: Accounting,
+
+    /// The history of the payments made by the treasury.
+    pub disbursements: Vector<Disbursement>,
+
+    /// The amounts received per fee kind and paid out, per approved fungible token.
+    pub token_accounting: UnorderedMap<AccountId, Accounting>,
+
+    /// The account ID of the parameter registry holding the approved fungible tokens, once set by the foundation.
+    pub parameter_registry_account_id: Option<AccountId>,
+}
+
+/// Message of `ft_transfer_call` paying a fee to the treasury in an approved fungible token.
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtFeeDeposit {
+    /// The kind of fee paid.
+    pub kind: FeeKind,
+    /// The purpose of the payment, e.g. the tender the fee is for.
+    pub memo: Option<String>,
+}
+
+/// External interface for the callbacks to self.
+#[ext_contract(ext_self)]
+pub trait ExtSelf {
+    fn on_token_checked(
+        &mut self,
+        token_account_id: AccountId,
+        sender_id: AccountId,
+        amount: U128,
+        deposit: FtFeeDeposit,
+    ) -> U128;
+
+    fn on_ft_disbursed(&mut self, token_account_id: AccountId, amount: U128, index: u64) -> bool;
+
+    fn on_near_disbursed(&mut self, amount: U128, index: u64) -> bool;
+}
+
+impl Default for Treasury {
+    fn default() -> Self {
+        Error::NotInitialized.panic()
+    }
+}
+
+#[near_bindgen]
+impl Treasury {
+    /// Initializes the treasury with the given Tenderbox foundation account ID.
+    #[init]
+    pub fn new(foundation_account_id: AccountId) -> Self {
+        require!(!env::state_exists(), Error::AlreadyInitialized);
+        require!(
+            env::is_valid_account_id(foundation_account_id.as_bytes()),
+            Error::InvalidFoundationAccountId
+        );
+        Self {
+            foundation: FoundationRole::new(foundation_account_id),
+            events: EventLog::new(),
+            accounting: Accounting::default(),
+            disbursements: Vector::new(StorageKey::Disbursements),
+            token_accounting: UnorderedMap::new(StorageKey::TokenAccounting),
+            parameter_registry_account_id: None,
+        }
+    }
+
+    /// Returns the account ID of the Tenderbox foundation.
+    pub fn get_foundation_account_id(&self) -> AccountId {
+        self.foundation.account_id().clone()
+    }
+
+    /// Returns the sequence number of the last event emitted by the contract, `0` if none was emitted yet.
+    pub fn get_last_event_seq(&self) -> u64 {
+        self.events.last_seq()
+    }
+
+    /// Returns the amounts received per fee kind, paid out and still available.
+    pub fn get_accounting(&self) -> AccountingView {
+        AccountingView {
+            posting_fees: self.accounting.posting_fees.into(),
+            verification_fees: self.accounting.verification_fees.into(),
+            award_fees: self.accounting.award_fees.into(),
+            other: self.accounting.other.into(),
+            disbursed: self.accounting.disbursed.into(),
+            available: self.internal_available_balance().into(),
+        }
+    }
+
+    /// Returns the amounts received per fee kind, paid out and still available in the given fungible token.
+    pub fn get_token_accounting(&self, token_account_id: AccountId) -> Option<AccountingView> {
+        self.token_accounting
+            .get(&token_account_id)
+            .map(|accounting| AccountingView {
+                posting_fees: accounting.posting_fees.into(),
+                verification_fees: accounting.verification_fees.into(),
+                award_fees: accounting.award_fees.into(),
+                other: accounting.other.into(),
+                disbursed: accounting.disbursed.into(),
+                available: (accounting.total_received() - accounting.disbursed).into(),
+            })
+    }
+
+    /// Returns the account ID of the parameter registry, if set.
+    pub fn get_parameter_registry(&self) -> Option<AccountId> {
+        self.parameter_registry_account_id.clone()
+    }
+
+    /// Returns the total number of payments made by the treasury.
+    pub fn get_number_of_disbursements(&self) -> u64 {
+        self.disbursements.len()
+    }
+
+    /// Returns up to `limit` payments made by the treasury, starting at `from_index`.
+    pub fn get_disbursements(&self, from_index: u64, limit: u64) -> Vec<Disbursement> {
+        (from_index..std::cmp::min(from_index + limit, self.disbursements.len()))
+            .map(|index| self.disbursements.get(index).unwrap())
+            .collect()
+    }
+
+    /// Records the attached deposit as income of the given fee kind.
+    #[payable]
+    pub fn deposit(&mut self, kind: FeeKind, memo: Option<String>) {
+        let amount = env::attached_deposit();
+        require!(amount > 0, Error::DepositNotPositive);
+        self.accounting.record(kind, amount);
+        self.events.emit(
+            format!(
+                "Received {} from @{}{}",
+                amount,
+                env::predecessor_account_id(),
+                memo.map(|memo| format!(": {}", memo)).unwrap_or_default()
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Receives a fee paid with `ft_transfer_call` in a fungible token. The message is a JSON
+    /// `FtFeeDeposit`. Tokens not approved in the parameter registry are returned to the sender.
+    pub fn ft_on_transfer(&mut self, sender_id: AccountId, amount: U128, msg: String) -> PromiseOrValue<U128> {
+        let deposit: FtFeeDeposit = near_sdk::serde_json::from_str(&msg)
+            .unwrap_or_else(|_| Error::InvalidFeeDepositMessage.panic());
+        let parameter_registry_account_id = self
+            .parameter_registry_account_id
+            .clone()
+            .unwrap_or_else(|| Error::TokensNotAccepted.panic());
+        let token_account_id = env::predecessor_account_id();
+        ext_parameter_registry::is_token_approved(
+            token_account_id.clone(),
+            &parameter_registry_account_id,
+            NO_DEPOSIT,
+            gas::parameter_registry::IS_TOKEN_APPROVED,
+        )
+        .then(ext_self::on_token_checked(
+            token_account_id,
+            sender_id,
+            amount,
+            deposit,
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            gas::CALLBACK,
+        ))
+        .into()
+    }
+
+    /// Callback after the parameter registry was asked about the received token.
+    /// Records the fee and returns `0` if the token is approved, returns the full amount to refund otherwise.
+    pub fn on_token_checked(
+        &mut self,
+        token_account_id: AccountId,
+        sender_id: AccountId,
+        amount: U128,
+        deposit: FtFeeDeposit,
+    ) -> U128 {
+        assert_self();
+        let token_approved = promise_result_as_bool();
+        if !token_approved {
+            self.events.emit(
+                format!(
+                    "Returning {} of @{} to @{}: the token is not approved",
+                    amount.0, token_account_id, sender_id
+                )
+                .as_bytes(),
+            );
+            return amount;
+        }
+        let mut accounting = self.token_accounting.get(&token_account_id).unwrap_or_default();
+        accounting.record(deposit.kind, amount.0);
+        self.token_accounting.insert(&token_account_id, &accounting);
+        self.events.emit(
+            format!(
+                "Received {} of @{} from @{}{}",
+                amount.0,
+                token_account_id,
+                sender_id,
+                deposit.memo.map(|memo| format!(": {}", memo)).unwrap_or_default()
+            )
+            .as_bytes(),
+        );
+        0.into()
+    }
+
+    /// Callback after the fungible token transfer of the disbursement with the given index.
+    /// Gives a failed transfer back to the token accounting and marks the disbursement failed.
+    /// Returns `true` if the transfer succeeded, `false` otherwise.
+    pub fn on_ft_disbursed(&mut self, token_account_id: AccountId, amount: U128, index: u64) -> bool {
+        assert_self();
+        if is_promise_success() {
+            return true;
+        }
+        if let Some(mut accounting) = self.token_accounting.get(&token_account_id) {
+            accounting.disbursed -= amount.0;
+            self.token_accounting.insert(&token_account_id, &accounting);
+        }
+        if let Some(mut disbursement) = self.disbursements.get(index) {
+            disbursement.failed = true;
+            self.disbursements.replace(index, &disbursement);
+        }
+        self.events.emit(
+            format!(
+                "The disbursement of {} of @{} failed and is back in the treasury",
+                amount.0, token_account_id
+            )
+            .as_bytes(),
+        );
+        false
+    }
+
+    /// Callback after the NEAR transfer of the disbursement with the given index.
+    /// Gives a failed transfer, which is refunded to the treasury, back to the accounting and marks the
+    /// disbursement failed.
+    /// Returns `true` if the transfer succeeded, `false` otherwise.
+    pub fn on_near_disbursed(&mut self, amount: U128, index: u64) -> bool {
+        assert_self();
+        if is_promise_success() {
+            return true;
+        }
+        self.accounting.disbursed -= amount.0;
+        if let Some(mut disbursement) = self.disbursements.get(index) {
+            disbursement.failed = true;
+            self.disbursements.replace(index, &disbursement);
+        }
+        self.events.emit(format!("The disbursement of {} failed and is back in the treasury", amount.0).as_bytes());
+        false
+    }
+
+    /**************/
+    /* Tenderbox Foundation */
+    /**************/
+
+    /// Sets the parameter registry holding the fungible tokens the treasury accepts.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn set_parameter_registry(&mut self, parameter_registry_account_id: AccountId) {
+        self.assert_called_by_foundation();
+        require!(
+            env::is_valid_account_id(parameter_registry_account_id.as_bytes()),
+            Error::InvalidAccountId
+        );
+        self.parameter_registry_account_id = Some(parameter_registry_account_id);
+    }
+
+    /// Pays the given amount to the given account ID for the purpose given in the memo.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn disburse(&mut self, to: AccountId, amount: U128, memo: String) -> Promise {
+        self.assert_called_by_foundation();
+        require!(env::is_valid_account_id(to.as_bytes()), Error::InvalidAccountId);
+        require!(amount.0 <= self.internal_available_balance(), Error::NotEnoughTreasuryBalance);
+        self.accounting.disbursed += amount.0;
+        self.events.emit(format!("Disbursing {} to @{}: {}", amount.0, to, memo).as_bytes());
+        self.disbursements.push(&Disbursement {
+            to: to.clone(),
+            amount,
+            token_account_id: None,
+            memo,
+            timestamp: env::block_timestamp().into(),
+            failed: false,
+        });
+        let index = self.disbursements.len() - 1;
+        Promise::new(to).transfer(amount.0).then(ext_self::on_near_disbursed(
+            amount,
+            index,
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            gas::CALLBACK,
+        ))
+    }
+
+    /// Pays the given amount of the given fungible token to the given account ID for the purpose given in the memo.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn disburse_ft(&mut self, token_account_id: AccountId, to: AccountId, amount: U128, memo: String) -> Promise {
+        self.assert_called_by_foundation();
+        require!(env::is_valid_account_id(to.as_bytes()), Error::InvalidAccountId);
+        let mut accounting = self
+            .token_accounting
+            .get(&token_account_id)
+            .unwrap_or_else(|| Error::UnknownToken.panic());
+        require!(
+            amount.0 <= accounting.total_received() - accounting.disbursed,
+            Error::NotEnoughTreasuryBalance
+        );
+        accounting.disbursed += amount.0;
+        self.token_accounting.insert(&token_account_id, &accounting);
+        self.events.emit(
+            format!(
+                "Disbursing {} of @{} to @{}: {}",
+                amount.0, token_account_id, to, memo
+            )
+            .as_bytes(),
+        );
+        self.disbursements.push(&Disbursement {
+            to: to.clone(),
+            amount,
+            token_account_id: Some(token_account_id.clone()),
+            memo: memo.clone(),
+            timestamp: env::block_timestamp().into(),
+            failed: false,
+        });
+        let index = self.disbursements.len() - 1;
+        ext_ft::ft_transfer(
+            to,
+            amount,
+            Some(memo),
+            &token_account_id,
+            ONE_YOCTO,
+            gas::ft::FT_TRANSFER,
+        )
+        .then(ext_self::on_ft_disbursed(
+            token_account_id,
+            amount,
+            index,
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            gas::CALLBACK,
+        ))
+    }
+
+    /// Proposes the given account ID, e.g. a Sputnik DAO, as the new Tenderbox foundation account.
+    /// The role is only handed over once the proposed account calls `accept_foundation_role`,
+    /// which a DAO does through a function-call proposal.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn propose_foundation_account_id(&mut self, foundation_account_id: AccountId) {
+        self.assert_called_by_foundation();
+        self.foundation.propose(foundation_account_id);
+    }
+
+    /// Accepts the Tenderbox foundation role proposed to the predecessor.
+    /// This method can only be called by the account proposed with `propose_foundation_account_id`.
+    pub fn accept_foundation_role(&mut self) {
+        self.foundation.accept();
+        self.events.emit(
+            format!(
+                "The Tenderbox foundation role was handed over to @{}",
+                self.foundation.account_id()
+            )
+            .as_bytes(),
+        );
+    }
+
+    /************/
+    /* Internal */
+    /************/
+
+    /// Internal method to verify the predecessor was the Tenderbox Foundation account ID.
+    fn assert_called_by_foundation(&self) {
+        self.foundation.assert_called_by();
+    }
+
+    /// Internal method returning the balance not needed for the storage of the treasury.
+    fn internal_available_balance(&self) -> Balance {
+        env::account_balance()
+            .saturating_sub(Balance::from(env::storage_usage()) * env::storage_byte_cost())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::{testing_env, MockedBlockchain, PromiseResult};
+
+    mod test_utils;
+    use proptest::prelude::*;
+    use test_utils::*;
+
+    #[test]
+    fn test_deposit_and_disburse() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_treasury())
+            .predecessor_account_id(account_factory())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = Treasury::new(account_tenderbox());
+
+        // Receiving a posting fee from the factory
+        context.attached_deposit = ntoy(1);
+        context.account_balance += ntoy(1);
+        testing_env!(context.clone());
+        contract.deposit(FeeKind::PostingFee, Some("@tender.factory".to_string()));
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_accounting().posting_fees.0, ntoy(1));
+
+        // Disbursing by the foundation
+        context.is_view = false;
+        context.attached_deposit = 0;
+        context.predecessor_account_id = account_tenderbox();
+        testing_env!(context.clone());
+        contract.disburse(account_tender_owner(), ntoy(1).into(), "Grant".to_string());
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_accounting().disbursed.0, ntoy(1));
+        assert_eq!(contract.get_number_of_disbursements(), 1);
+        assert_eq!(contract.get_disbursements(0, 10)[0].to, account_tender_owner());
+        assert_eq!(contract.get_last_event_seq(), 2);
+    }
+
+    #[test]
+    fn test_disburse_failed() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_treasury())
+            .predecessor_account_id(account_factory())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = Treasury::new(account_tenderbox());
+        context.attached_deposit = ntoy(1);
+        testing_env!(context.clone());
+        contract.deposit(FeeKind::PostingFee, None);
+
+        context.attached_deposit = 0;
+        context.predecessor_account_id = account_tenderbox();
+        testing_env!(context.clone());
+        contract.disburse(account_tender_owner(), ntoy(1).into(), "Grant".to_string());
+
+        // The failed transfer gives the amount back
+        context.predecessor_account_id = account_treasury();
+        testing_env_with_promise_results(context.clone(), PromiseResult::Failed);
+        assert!(!contract.on_near_disbursed(ntoy(1).into(), 0));
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_accounting().disbursed.0, 0);
+        assert!(contract.get_disbursements(0, 10)[0].failed);
+    }
+
+    #[test]
+    #[should_panic(expected = "E101: Can only be called by the Tenderbox Foundation")]
+    fn test_disburse_not_by_foundation() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_treasury())
+            .predecessor_account_id(account_factory())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = Treasury::new(account_tenderbox());
+        contract.disburse(account_factory(), ntoy(1).into(), "Withdrawal".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "E604: Not enough available balance in the treasury")]
+    fn test_disburse_more_than_available() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_treasury())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = Treasury::new(account_tenderbox());
+        contract.disburse(account_tender_owner(), (context.account_balance + 1).into(), "Grant".to_string());
+    }
+
+    #[test]
+    fn test_ft_fee_deposit() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_treasury())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = Treasury::new(account_tenderbox());
+        contract.set_parameter_registry(account_parameter_registry());
+
+        // Receiving a verification fee in a stablecoin
+        context.predecessor_account_id = account_stablecoin();
+        testing_env!(context.clone());
+        contract.ft_on_transfer(
+            account_supplier(),
+            ntoy(5).into(),
+            r#"{"kind":"VerificationFee","memo":null}"#.to_string(),
+        );
+
+        context.predecessor_account_id = account_treasury();
+        testing_env_with_promise_results(context.clone(), PromiseResult::Successful(b"true".to_vec()));
+        let unused = contract.on_token_checked(
+            account_stablecoin(),
+            account_supplier(),
+            ntoy(5).into(),
+            FtFeeDeposit {
+                kind: FeeKind::VerificationFee,
+                memo: None,
+            },
+        );
+        assert_eq!(unused.0, 0);
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        let accounting = contract.get_token_accounting(account_stablecoin()).unwrap();
+        assert_eq!(accounting.verification_fees.0, ntoy(5));
+        assert_eq!(accounting.available.0, ntoy(5));
+    }
+
+    #[test]
+    fn test_disburse_ft_failed() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_treasury())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = Treasury::new(account_tenderbox());
+        let mut accounting = Accounting::default();
+        accounting.record(FeeKind::VerificationFee, ntoy(5));
+        contract.token_accounting.insert(&account_stablecoin(), &accounting);
+
+        contract.disburse_ft(account_stablecoin(), account_tender_owner(), ntoy(2).into(), "Grant".to_string());
+        assert_eq!(contract.get_token_accounting(account_stablecoin()).unwrap().available.0, ntoy(3));
+
+        // The failed transfer gives the amount back
+        context.predecessor_account_id = account_treasury();
+        testing_env_with_promise_results(context.clone(), PromiseResult::Failed);
+        assert!(!contract.on_ft_disbursed(account_stablecoin(), ntoy(2).into(), 0));
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        let accounting = contract.get_token_accounting(account_stablecoin()).unwrap();
+        assert_eq!(accounting.disbursed.0, 0);
+        assert_eq!(accounting.available.0, ntoy(5));
+        assert!(contract.get_disbursements(0, 10)[0].failed);
+    }
+
+    #[test]
+    fn test_ft_fee_deposit_token_not_approved() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_treasury())
+            .predecessor_account_id(account_treasury())
+            .finish();
+        testing_env_with_promise_results(context.clone(), PromiseResult::Successful(b"false".to_vec()));
+
+        let mut contract = Treasury::new(account_tenderbox());
+        let unused = contract.on_token_checked(
+            account_stablecoin(),
+            account_supplier(),
+            ntoy(5).into(),
+            FtFeeDeposit {
+                kind: FeeKind::PostingFee,
+                memo: None,
+            },
+        );
+        assert_eq!(unused.0, ntoy(5));
+        assert!(contract.get_token_accounting(account_stablecoin()).is_none());
+    }
+
+    fn fee_kind() -> impl Strategy<Value = FeeKind> {
+        prop_oneof![
+            Just(FeeKind::PostingFee),
+            Just(FeeKind::VerificationFee),
+            Just(FeeKind::AwardFee),
+            Just(FeeKind::Other),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_accounting_totals(deposits in prop::collection::vec((fee_kind(), 1..=ntoy(1_000)), 0..50)) {
+            let mut accounting = Accounting::default();
+            for (kind, amount) in deposits.iter() {
+                accounting.record(*kind, *amount);
+            }
+            let total: Balance = deposits.iter().map(|(_, amount)| amount).sum();
+            prop_assert_eq!(accounting.total_received(), total);
+            let posting_fees: Balance = deposits
+                .iter()
+                .filter(|(kind, _)| *kind == FeeKind::PostingFee)
+                .map(|(_, amount)| amount)
+                .sum();
+            prop_assert_eq!(accounting.posting_fees, posting_fees);
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{Base58PublicKey, U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, AccountId};
+
+use crate::errors::*;
+
+// Types exchanged between the Tenderbox contracts. Every contract includes this module instead of
+// keeping its own copy, so the field names and encodings can't drift apart.
+
+/// Arguments of the `new` method of a tender, passed by the factory that deploys it.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TenderParameters {
+    /// Owner account ID of the tender issued.
+    pub owner_id: AccountId,
+    /// Public key initiated to secure the tender.
+    pub tender_public_key: Base58PublicKey,
+    /// Tender proposal statement.
+    pub tender_proposal: String,
+    /// Products/services needed.
+    pub line_items: Vec<LineItem>,
+    /// Industry/Sector of the Tender originator.
+    pub industry: String,
+    /// Delivery points of the line items, each with its own quantities and due date.
+    pub delivery_points: Vec<DeliveryPoint>,
+    /// Award criteria, fixed at creation.
+    pub criteria: EvaluationCriteria,
+    /// Whether the tender is fast-tracked, e.g. for disaster response. Its submission and challenge
+    /// windows shrink to the emergency minimums of the platform parameters.
+    pub emergency: bool,
+    /// Payment schedule of the tender, expanded from a milestone template.
+    pub milestones: Vec<Milestone>,
+    /// Block timestamp in nanoseconds the tender opens at, if it was announced in advance.
+    /// Until then the tender is Scheduled and accepts no bids.
+    pub opens_at: Option<U64>,
+}
+
+/// Product/service needed by a tender.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct LineItem {
+    /// Product/service needed.
+    pub product: String,
+    /// Budgeted unit price of the product/service.
+    pub unit_budget: U128,
+    /// Quantity of product/service needed.
+    pub quantity: u64,
+}
+
+/// Place a part of the quantities of a tender has to be delivered to.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DeliveryPoint {
+    /// Location of delivery, e.g. the address of a branch.
+    pub location: String,
+    /// Quantity of each line item to deliver there, in the order of the line items.
+    pub quantities: Vec<u64>,
+    /// Block timestamp in nanoseconds the delivery is due by.
+    pub due_at: U64,
+}
+
+/// How the winning bid of a tender is selected.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AwardMode {
+    /// The compliant bid with the lowest price wins.
+    LowestPrice,
+    /// The compliant bid with the highest weighted score wins.
+    WeightedScore,
+}
+
+/// Criterion a bid is scored on, with its share of the total score.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CriterionWeight {
+    /// Name of the criterion, e.g. "Price" or "Delivery time".
+    pub name: String,
+    /// Share of the total score, in basis points.
+    pub weight_bps: u16,
+}
+
+/// Award criteria of a tender. They are fixed at creation and committed to by their hash, so
+/// bidders can check the rules they bid under were not changed.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EvaluationCriteria {
+    pub mode: AwardMode,
+    /// Scored criteria. Only used, and then required, in the `WeightedScore` mode.
+    pub weights: Vec<CriterionWeight>,
+    /// Requirements a bid has to meet to be compliant, whatever its price or score.
+    pub mandatory_requirements: Vec<String>,
+}
+
+impl EvaluationCriteria {
+    /// Asserts the weights match the award mode.
+    pub fn assert_valid(&self) {
+        match self.mode {
+            AwardMode::LowestPrice => {
+                require!(self.weights.is_empty(), Error::UnexpectedCriterionWeights);
+            }
+            AwardMode::WeightedScore => {
+                require!(
+                    self.weights.iter().map(|weight| weight.weight_bps as u32).sum::<u32>() == 10_000,
+                    Error::CriterionWeightsMismatch
+                );
+            }
+        }
+    }
+
+    /// Returns the sha256 hash of the Borsh-encoded criteria.
+    pub fn hash(&self) -> Vec<u8> {
+        env::sha256(&self.try_to_vec().unwrap())
+    }
+}
+
+/// Step of the payment schedule of a tender.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Milestone {
+    /// What has to be delivered to reach the milestone.
+    pub description: String,
+    /// Share of the tender value paid out on reaching the milestone, in basis points.
+    pub payment_bps: u16,
+}
+
+/// How a tender ended, reported to its factory when it is closed or finalized.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum TenderOutcome {
+    /// The awarded supplier delivered and was paid the final amount.
+    Completed { winner_id: AccountId, final_amount: U128 },
+    /// The tender was cancelled, or ended without an award.
+    Cancelled,
+}
+
+/// The kind of fee a deposit to the treasury comes from.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum FeeKind {
+    /// Fee for posting a tender, forwarded by a tender factory.
+    PostingFee,
+    /// Fee for verifying a tender, factory or supplier.
+    VerificationFee,
+    /// Protocol cut of an awarded contract value, forwarded by a tender.
+    AwardFee,
+    /// Any other income.
+    Other,
+}
+
+/// Platform-wide parameters of the parameter registry, read by the factories and the tenders.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PlatformParameters {
+    /// Minimum amount of tokens to attach to `create_tender`, on top of the posting fee.
+    pub min_attached_balance: U128,
+    /// Part of the attached deposit of `create_tender` kept as posting fee.
+    pub posting_fee: U128,
+    /// Protocol cut of an awarded contract value, in basis points.
+    pub award_fee_bps: u16,
+    /// Gas the factories attach to the initialization of a new tender.
+    pub tender_new_gas: U64,
+    /// Time in nanoseconds a party can open a dispute after a milestone was delivered.
+    pub dispute_window: U64,
+    /// Minimum time in nanoseconds an emergency tender stays open for bids.
+    pub emergency_submission_window: U64,
+    /// Minimum time in nanoseconds the award of an emergency tender can be challenged.
+    pub emergency_challenge_window: U64,
+}
+
+impl PlatformParameters {
+    /// Asserts the parameters are within bounds.
+    pub fn assert_valid(&self) {
+        require!(self.award_fee_bps <= 10_000, Error::AwardFeeTooHigh);
+        require!(self.tender_new_gas.0 > 0, Error::TenderGasNotPositive);
+    }
+}
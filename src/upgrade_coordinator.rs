@@ -0,0 +1,477 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::UnorderedMap;
+use near_sdk::json_types::{Base64VecU8, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, ext_contract, near_bindgen, AccountId, Promise};
+
+
+// The upgrade coordinator keeps the code versions approved by the Tenderbox foundation and rolls
+// them out to the verify tender contract, the factories and the tenders that consented to it.
+
+#[macro_use]
+mod errors;
+use crate::errors::*;
+
+mod foundation;
+use crate::foundation::*;
+
+mod types;
+
+mod interfaces;
+use crate::interfaces::*;
+
+mod events;
+use crate::events::*;
+
+mod storage;
+use crate::storage::*;
+
+mod utils;
+use crate::utils::*;
+
+
+// The maximum number of contracts upgraded in a single batch, so the attached gas stays in bounds.
+// Every upgrade takes 100 Tgas with its migration and callback, out of the 300 Tgas of a transaction.
+const MAX_UPGRADE_BATCH: usize = 2;
+
+pub mod gas {
+    use near_sdk::Gas;
+
+    pub use crate::interfaces::gas::*;
+
+    /// The amount of Gas the contract will attach to the callbacks to itself, recording an upgrade or a consent.
+    /// The base for the execution.
+    pub const CALLBACK: Gas = BASE;
+}
+
+/// Kind of contract of the Tenderbox contract family.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ContractKind {
+    VerifyTender,
+    Factory,
+    Tender,
+}
+
+/// Code version approved by the Tenderbox foundation.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CodeVersion {
+    /// The kind of contract the code is for.
+    pub kind: ContractKind,
+    /// The sha256 hash of the code.
+    pub code_hash: Base64VecU8,
+    /// The block timestamp in nanoseconds at which the version was approved.
+    pub approved_at: U64,
+}
+
+/// Contract managed by the coordinator.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ManagedContract {
+    /// The kind of the contract.
+    pub kind: ContractKind,
+    /// The version the contract runs, if it was upgraded by the coordinator.
+    pub version: Option<String>,
+}
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct UpgradeCoordinator {
+    /// The Tenderbox foundation role. It approves the versions and runs the upgrades.
+    pub foundation: FoundationRole,
+
+    /// The sequence of the events emitted by the contract.
+    pub events: EventLog,
+
+    /// The account ID of the verify tender contract. Only verified tenders can consent to upgrades.
+    pub verify_tender_account_id: AccountId,
+
+    /// The approved code versions by version name.
+    pub versions: UnorderedMap<String, CodeVersion>,
+
+    /// The contracts managed by the coordinator and the version they run.
+    pub contracts: UnorderedMap<AccountId, ManagedContract>,
+}
+
+/// External interface for the callbacks to self.
+#[ext_contract(ext_self)]
+pub trait ExtSelf {
+    fn on_upgraded(&mut self, account_id: AccountId, version: String) -> bool;
+
+    fn on_consent_authenticated(&mut self, tender_account_id: AccountId) -> bool;
+}
+
+impl Default for UpgradeCoordinator {
+    fn default() -> Self {
+        Error::NotInitialized.panic()
+    }
+}
+
+#[near_bindgen]
+impl UpgradeCoordinator {
+    /// Initializes the coordinator with the given Tenderbox foundation account ID and the account ID
+    /// of the verify tender contract.
+    #[init]
+    pub fn new(foundation_account_id: AccountId, verify_tender_account_id: AccountId) -> Self {
+        require!(!env::state_exists(), Error::AlreadyInitialized);
+        require!(
+            env::is_valid_account_id(foundation_account_id.as_bytes()),
+            Error::InvalidFoundationAccountId
+        );
+        require!(
+            env::is_valid_account_id(verify_tender_account_id.as_bytes()),
+            Error::InvalidVerifyTenderAccountId
+        );
+        Self {
+            foundation: FoundationRole::new(foundation_account_id),
+            events: EventLog::new(),
+            verify_tender_account_id,
+            versions: UnorderedMap::new(StorageKey::CodeVersions),
+            contracts: UnorderedMap::new(StorageKey::ManagedContracts),
+        }
+    }
+
+    /// Returns the account ID of the Tenderbox foundation.
+    pub fn get_foundation_account_id(&self) -> AccountId {
+        self.foundation.account_id().clone()
+    }
+
+    /// Returns the sequence number of the last event emitted by the contract, `0` if none was emitted yet.
+    pub fn get_last_event_seq(&self) -> u64 {
+        self.events.last_seq()
+    }
+
+    /// Returns the approved code version with the given name, if any.
+    pub fn get_version(&self, version: String) -> Option<CodeVersion> {
+        self.versions.get(&version)
+    }
+
+    /// Returns up to `limit` approved code versions, starting at `from_index`.
+    pub fn get_versions(&self, from_index: u64, limit: u64) -> Vec<(String, CodeVersion)> {
+        let keys = self.versions.keys_as_vector();
+        let values = self.versions.values_as_vector();
+        (from_index..std::cmp::min(from_index + limit, keys.len()))
+            .map(|index| (keys.get(index).unwrap(), values.get(index).unwrap()))
+            .collect()
+    }
+
+    /// Returns the managed contract with the given account ID, if any.
+    pub fn get_contract(&self, account_id: AccountId) -> Option<ManagedContract> {
+        self.contracts.get(&account_id)
+    }
+
+    /// Returns up to `limit` managed contracts and the version they run, starting at `from_index`.
+    pub fn get_contracts(&self, from_index: u64, limit: u64) -> Vec<(AccountId, ManagedContract)> {
+        let keys = self.contracts.keys_as_vector();
+        let values = self.contracts.values_as_vector();
+        (from_index..std::cmp::min(from_index + limit, keys.len()))
+            .map(|index| (keys.get(index).unwrap(), values.get(index).unwrap()))
+            .collect()
+    }
+
+    /// Consents to upgrades of the predecessor by the coordinator.
+    /// The consent is only recorded once the verify tender contract confirms the predecessor is a verified tender.
+    /// This method is called by a tender on behalf of its owner.
+    pub fn consent_to_upgrades(&mut self) -> Promise {
+        let tender_account_id = env::predecessor_account_id();
+        ext_verify_tender::is_verified(
+            tender_account_id.clone(),
+            &self.verify_tender_account_id,
+            NO_DEPOSIT,
+            gas::verify_tender::IS_VERIFIED,
+        )
+        .then(ext_self::on_consent_authenticated(
+            tender_account_id,
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            gas::CALLBACK,
+        ))
+    }
+
+    /// Callback after the verify tender contract was asked about the consenting tender.
+    /// Returns `true` if the tender is verified and was not managed before, `false` otherwise.
+    pub fn on_consent_authenticated(&mut self, tender_account_id: AccountId) -> bool {
+        assert_self();
+        let tender_verified = promise_result_as_bool();
+        if !tender_verified {
+            self.events.emit(
+                format!(
+                    "Ignoring the upgrade consent from @{}: it is not a verified tender",
+                    tender_account_id
+                )
+                .as_bytes(),
+            );
+            return false;
+        }
+        if self.contracts.get(&tender_account_id).is_some() {
+            return false;
+        }
+        self.contracts.insert(
+            &tender_account_id,
+            &ManagedContract {
+                kind: ContractKind::Tender,
+                version: None,
+            },
+        );
+        true
+    }
+
+    /// Withdraws the consent to upgrades of the predecessor by the coordinator.
+    /// This method is called by a tender on behalf of its owner.
+    pub fn withdraw_upgrade_consent(&mut self) -> bool {
+        let tender_account_id = env::predecessor_account_id();
+        match self.contracts.get(&tender_account_id) {
+            Some(contract) if contract.kind == ContractKind::Tender => {
+                self.contracts.remove(&tender_account_id);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Callback after the upgrade of the given contract. Records the version it runs and returns
+    /// `true` if the upgrade succeeded, returns `false` otherwise.
+    pub fn on_upgraded(&mut self, account_id: AccountId, version: String) -> bool {
+        assert_self();
+        if is_promise_success() {
+            if let Some(mut contract) = self.contracts.get(&account_id) {
+                contract.version = Some(version.clone());
+                self.contracts.insert(&account_id, &contract);
+            }
+            self.events.emit(format!("@{} runs version {}", account_id, version).as_bytes());
+            true
+        } else {
+            self.events.emit(format!("The upgrade of @{} to version {} failed", account_id, version).as_bytes());
+            false
+        }
+    }
+
+    /**************/
+    /* Tenderbox Foundation */
+    /**************/
+
+    /// Approves the code with the given sha256 hash as the given version for the given kind of contract.
+    /// Returns `true` if the version name was not taken yet.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn approve_version(&mut self, version: String, kind: ContractKind, code_hash: Base64VecU8) -> bool {
+        self.assert_called_by_foundation();
+        require!(!version.is_empty(), Error::EmptyVersion);
+        require!(code_hash.0.len() == 32, Error::InvalidCodeHash);
+        if self.versions.get(&version).is_some() {
+            return false;
+        }
+        self.versions.insert(
+            &version,
+            &CodeVersion {
+                kind,
+                code_hash,
+                approved_at: env::block_timestamp().into(),
+            },
+        );
+        true
+    }
+
+    /// Revokes the approval of the given version. Returns `true` if it was approved.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn revoke_version(&mut self, version: String) -> bool {
+        self.assert_called_by_foundation();
+        self.versions.remove(&version).is_some()
+    }
+
+    /// Adds the verify tender contract or a factory to the managed contracts.
+    /// Tenders are only managed after they consented with `consent_to_upgrades`.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn add_contract(&mut self, account_id: AccountId, kind: ContractKind) -> bool {
+        self.assert_called_by_foundation();
+        require!(env::is_valid_account_id(account_id.as_bytes()), Error::InvalidAccountId);
+        require!(kind != ContractKind::Tender, Error::TenderConsentRequired);
+        if self.contracts.get(&account_id).is_some() {
+            return false;
+        }
+        self.contracts.insert(&account_id, &ManagedContract { kind, version: None });
+        true
+    }
+
+    /// Removes the given contract from the managed contracts.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn remove_contract(&mut self, account_id: AccountId) -> bool {
+        self.assert_called_by_foundation();
+        self.contracts.remove(&account_id).is_some()
+    }
+
+    /// Deploys the given code of an approved version on the given managed contracts in a batch.
+    /// The contracts have to be of the kind the version was approved for.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn upgrade(&mut self, version: String, code: Base64VecU8, account_ids: Vec<AccountId>) -> Promise {
+        self.assert_called_by_foundation();
+        let code_version = self
+            .versions
+            .get(&version)
+            .unwrap_or_else(|| Error::VersionNotApproved.panic());
+        require!(env::sha256(&code.0) == code_version.code_hash.0, Error::CodeHashMismatch);
+        require!(
+            !account_ids.is_empty() && account_ids.len() <= MAX_UPGRADE_BATCH,
+            Error::UpgradeBatchSize(MAX_UPGRADE_BATCH)
+        );
+        for account_id in account_ids.iter() {
+            let contract = self
+                .contracts
+                .get(account_id)
+                .unwrap_or_else(|| Error::ContractNotManaged.panic());
+            require!(contract.kind == code_version.kind, Error::VersionKindMismatch);
+            self.events.emit(format!("Upgrading @{} to version {}", account_id, version).as_bytes());
+        }
+        account_ids
+            .into_iter()
+            .map(|account_id| {
+                ext_upgradable::upgrade(code.clone(), &account_id, NO_DEPOSIT, gas::upgradable::UPGRADE).then(
+                    ext_self::on_upgraded(
+                        account_id,
+                        version.clone(),
+                        &env::current_account_id(),
+                        NO_DEPOSIT,
+                        gas::CALLBACK,
+                    ),
+                )
+            })
+            .reduce(|batch, promise| batch.and(promise))
+            .unwrap()
+    }
+
+    /// Proposes the given account ID, e.g. a Sputnik DAO, as the new Tenderbox foundation account.
+    /// The role is only handed over once the proposed account calls `accept_foundation_role`,
+    /// which a DAO does through a function-call proposal.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn propose_foundation_account_id(&mut self, foundation_account_id: AccountId) {
+        self.assert_called_by_foundation();
+        self.foundation.propose(foundation_account_id);
+    }
+
+    /// Accepts the Tenderbox foundation role proposed to the predecessor.
+    /// This method can only be called by the account proposed with `propose_foundation_account_id`.
+    pub fn accept_foundation_role(&mut self) {
+        self.foundation.accept();
+        self.events.emit(
+            format!(
+                "The Tenderbox foundation role was handed over to @{}",
+                self.foundation.account_id()
+            )
+            .as_bytes(),
+        );
+    }
+
+    /************/
+    /* Internal */
+    /************/
+
+    /// Internal method to verify the predecessor was the Tenderbox Foundation account ID.
+    fn assert_called_by_foundation(&self) {
+        self.foundation.assert_called_by();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::{testing_env, MockedBlockchain, PromiseResult};
+
+    mod test_utils;
+    use test_utils::*;
+
+    #[test]
+    fn test_upgrade_factory() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_upgrade_coordinator())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = UpgradeCoordinator::new(account_tenderbox(), account_verify_tender());
+        let code = vec![0u8; 16];
+        assert!(contract.approve_version(
+            "factory-1.1.0".to_string(),
+            ContractKind::Factory,
+            env::sha256(&code).into()
+        ));
+        assert!(contract.add_contract(account_factory(), ContractKind::Factory));
+
+        contract.upgrade(
+            "factory-1.1.0".to_string(),
+            code.into(),
+            vec![account_factory()],
+        );
+
+        // Recording the version after the upgrade succeeded
+        context.predecessor_account_id = account_upgrade_coordinator();
+        testing_env_with_promise_results(context.clone(), PromiseResult::Successful(vec![]));
+        assert!(contract.on_upgraded(account_factory(), "factory-1.1.0".to_string()));
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(
+            contract.get_contract(account_factory()).unwrap().version,
+            Some("factory-1.1.0".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "E529: The version is approved for another kind of contract")]
+    fn test_upgrade_wrong_kind() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_upgrade_coordinator())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = UpgradeCoordinator::new(account_tenderbox(), account_verify_tender());
+        let code = vec![0u8; 16];
+        contract.approve_version(
+            "tender-2.0.0".to_string(),
+            ContractKind::Tender,
+            env::sha256(&code).into(),
+        );
+        contract.add_contract(account_verify_tender(), ContractKind::VerifyTender);
+        contract.upgrade(
+            "tender-2.0.0".to_string(),
+            code.into(),
+            vec![account_verify_tender()],
+        );
+    }
+
+    #[test]
+    fn test_tender_consent() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_upgrade_coordinator())
+            .predecessor_account_id(account_tender())
+            .finish();
+        testing_env!(context.clone());
+
+        // The tender consents, which is authenticated by the verify tender contract
+        let mut contract = UpgradeCoordinator::new(account_tenderbox(), account_verify_tender());
+        contract.consent_to_upgrades();
+
+        context.predecessor_account_id = account_upgrade_coordinator();
+        testing_env_with_promise_results(context.clone(), PromiseResult::Successful(b"true".to_vec()));
+        assert!(contract.on_consent_authenticated(account_tender()));
+        assert!(!contract.on_consent_authenticated(account_tender()));
+
+        // A consent from an account that isn't a verified tender is ignored
+        testing_env_with_promise_results(context.clone(), PromiseResult::Successful(b"false".to_vec()));
+        assert!(!contract.on_consent_authenticated(account_tender_owner()));
+        assert!(contract.get_contract(account_tender_owner()).is_none());
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(
+            contract.get_contract(account_tender()).unwrap().kind,
+            ContractKind::Tender
+        );
+
+        context.is_view = false;
+        context.predecessor_account_id = account_tender();
+        testing_env!(context.clone());
+        assert!(contract.withdraw_upgrade_consent());
+        assert!(contract.get_contract(account_tender()).is_none());
+    }
+}
@@ -0,0 +1,29 @@
+use near_sdk::{env, Balance, PromiseResult};
+
+use crate::errors::*;
+
+pub const NO_DEPOSIT: Balance = 0;
+
+/// Asserts the method is called by the contract itself, e.g. as a callback.
+pub fn assert_self() {
+    require!(env::predecessor_account_id() == env::current_account_id(), Error::NotSelf);
+}
+
+/// Returns `true` if the single promise this callback waited on succeeded.
+pub fn is_promise_success() -> bool {
+    require!(env::promise_results_count() == 1, Error::PromiseResultExpected);
+    match env::promise_result(0) {
+        PromiseResult::Successful(_) => true,
+        _ => false,
+    }
+}
+
+/// Returns the `bool` returned by the single promise this callback waited on, `false` if the promise failed
+/// or returned something else.
+pub fn promise_result_as_bool() -> bool {
+    require!(env::promise_results_count() == 1, Error::PromiseResultExpected);
+    match env::promise_result(0) {
+        PromiseResult::Successful(value) => near_sdk::serde_json::from_slice::<bool>(&value).unwrap_or(false),
+        _ => false,
+    }
+}
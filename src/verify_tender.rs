@@ -1,224 +1,1750 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::LookupSet;
-use near_sdk::{env, near_bindgen, AccountId};
+use near_sdk::collections::{LookupMap, LookupSet, Vector};
+use near_sdk::json_types::{Base58PublicKey, Base64VecU8, U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, ext_contract, near_bindgen, AccountId, Balance, Promise};
 
-#[global_allocator]
-static ALLOC: near_sdk::wee_alloc::WeeAlloc = near_sdk::wee_alloc::WeeAlloc::INIT;
+#[macro_use]
+mod errors;
+use crate::errors::*;
 
+mod foundation;
+use crate::foundation::*;
+
+mod timelock;
+use crate::timelock::*;
+
+mod events;
+use crate::events::*;
+
+mod storage;
+use crate::storage::*;
+
+mod utils;
+use crate::utils::*;
+
+// The maximum number of accounts imported from a legacy whitelist in a single batch, so the attached
+// gas stays in bounds.
+const MAX_IMPORT_BATCH: usize = 10;
+
+pub mod gas {
+    use near_sdk::Gas;
+
+    /// The base amount of gas for a regular execution.
+    const BASE: Gas = 25_000_000_000_000;
+
+    /// The amount of Gas the contract will attach to the promise to the legacy whitelist contract.
+    /// The base for the execution.
+    pub const LEGACY_WHITELIST: Gas = BASE;
+
+    /// The amount of Gas the contract will attach to the callback importing a single account.
+    /// The base for the execution.
+    pub const IMPORT_CALLBACK: Gas = BASE;
+
+    /// The amount of Gas the contract will attach to the migration of its state after an upgrade.
+    /// The base for the execution.
+    pub const MIGRATE: Gas = BASE;
+}
+
+/// External interface for the legacy whitelist contracts, modeled after the NEAR staking pool whitelist.
+#[ext_contract(ext_legacy_whitelist)]
+pub trait ExtLegacyWhitelist {
+    fn is_whitelisted(&self, staking_pool_account_id: AccountId) -> bool;
+}
+
+/// External interface for the callbacks to self.
+#[ext_contract(ext_self)]
+pub trait ExtSelf {
+    fn on_legacy_entry_checked(
+        &mut self,
+        tender_account_id: AccountId,
+        legacy_account_id: AccountId,
+    ) -> bool;
+}
 
 // Foundation referred in the contracts is the Tenderbox foundation/comp// any that is in charge of the whole Tendering platform
 
-#[near_bindgen]
-#[derive(BorshDeserialize, BorshSerialize)]
-pub struct VerifyTenderContract {
-    /// The account ID of the Tenderbox. It allows to automatically approve and secure newly created Tenders.
-    /// We can also verify newly created Tender Factory instances.
-    pub foundation_account_id: AccountId,
+/// Evidence backing the verification of a tender or factory, e.g. an audit report or a business registration.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VerificationMetadata {
+    /// Where the evidence is published.
+    pub url: String,
+    /// The sha256 hash of the published document, so it can't be swapped after the fact.
+    pub content_hash: Base64VecU8,
+}
+
+/// Stored versions of the verification metadata. Entries are upgraded to the current
+/// `VerificationMetadata` when read and stored as the latest version when written, so a new layout
+/// doesn't need a migration of every entry: add a variant holding the new layout and convert the
+/// previous ones in `From`.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum VersionedVerificationMetadata {
+    V1(VerificationMetadata),
+}
+
+impl From<VersionedVerificationMetadata> for VerificationMetadata {
+    fn from(metadata: VersionedVerificationMetadata) -> Self {
+        match metadata {
+            VersionedVerificationMetadata::V1(metadata) => metadata,
+        }
+    }
+}
+
+impl From<VerificationMetadata> for VersionedVerificationMetadata {
+    fn from(metadata: VerificationMetadata) -> Self {
+        VersionedVerificationMetadata::V1(metadata)
+    }
+}
+
+/// Stored record of a verified factory.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct FactoryRecord {
+    /// The block timestamp in nanoseconds the factory was verified at.
+    pub verified_at: u64,
+    /// The account ID that verified the factory.
+    pub verified_by: AccountId,
+    /// The industries the factory is meant to issue tenders for, empty if unrestricted.
+    pub industries: Vec<String>,
+    /// The maximum number of tenders the factory can reserve for verification, if limited.
+    pub tender_quota: Option<u64>,
+    /// The number of tenders the factory verified or reserved for verification.
+    pub tenders_verified: u64,
+}
+
+/// Stored versions of the factory records, see `VersionedVerificationMetadata`.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum VersionedFactoryRecord {
+    V1(FactoryRecord),
+}
+
+impl From<VersionedFactoryRecord> for FactoryRecord {
+    fn from(record: VersionedFactoryRecord) -> Self {
+        match record {
+            VersionedFactoryRecord::V1(record) => record,
+        }
+    }
+}
+
+impl From<FactoryRecord> for VersionedFactoryRecord {
+    fn from(record: FactoryRecord) -> Self {
+        VersionedFactoryRecord::V1(record)
+    }
+}
+
+/// Verified factory returned by `get_factory_record`, for the oversight of third-party factories.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VerifiedFactoryView {
+    /// The block timestamp in nanoseconds the factory was verified at.
+    pub verified_at: U64,
+    /// The account ID that verified the factory.
+    pub verified_by: AccountId,
+    /// The industries the factory is meant to issue tenders for, empty if unrestricted.
+    pub industries: Vec<String>,
+    /// The maximum number of tenders the factory can reserve for verification, if limited.
+    pub tender_quota: Option<U64>,
+    /// The number of tenders the factory verified or reserved for verification.
+    pub tenders_verified: U64,
+}
+
+/// Bounty funded to prioritize the review of a tender waiting for verification.
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ReviewBounty {
+    /// The accounts that funded the bounty, with the amount each of them can still withdraw.
+    pub funders: Vec<(AccountId, Balance)>,
+}
+
+impl ReviewBounty {
+    /// Returns the total amount of the bounty.
+    pub fn amount(&self) -> Balance {
+        self.funders.iter().map(|(_, amount)| amount).sum()
+    }
+}
+
+/// Stored versions of the review bounties, see `VersionedVerificationMetadata`.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum VersionedReviewBounty {
+    V1(ReviewBounty),
+}
+
+impl From<VersionedReviewBounty> for ReviewBounty {
+    fn from(bounty: VersionedReviewBounty) -> Self {
+        match bounty {
+            VersionedReviewBounty::V1(bounty) => bounty,
+        }
+    }
+}
+
+impl From<ReviewBounty> for VersionedReviewBounty {
+    fn from(bounty: ReviewBounty) -> Self {
+        VersionedReviewBounty::V1(bounty)
+    }
+}
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct VerifyTenderContract {
+    /// The Tenderbox foundation role. It allows to automatically approve and secure newly created Tenders.
+    /// We can also verify newly created Tender Factory instances.
+    pub foundation: FoundationRole,
+
+    /// The verified account IDs of approved Tender contracts.
+    pub verified: LookupSet<AccountId>,
+
+    /// The verified list of Tender factories. Any account from this lis   ///t can verify tenders.
+    pub factory_verified: LookupSet<AccountId>,
+
+    /// The verified account IDs of vetted suppliers. Tenders can restrict bidding to accounts from this list.
+    pub supplier_verified: LookupSet<AccountId>,
+
+    /// The registry of KYC attesters and the public keys they sign bidder attestations with.
+    /// Tenders requiring KYC only accept attestations signed by an attester from this registry.
+    pub attesters: LookupMap<AccountId, Base58PublicKey>,
+
+    /// The account ID of the supplier registry contract holding supplier profiles, once set by the foundation.
+    pub supplier_registry_account_id: Timelocked<Option<AccountId>>,
+
+    /// The account ID of the upgrade coordinator allowed to deploy approved code on this contract, once set by the foundation.
+    pub upgrade_coordinator_account_id: Timelocked<Option<AccountId>>,
+
+    /// The delay configuration changes wait before taking effect, in nanoseconds.
+    pub timelock_delay: Timelocked<u64>,
+
+    /// The reason the foundation tripped the platform-wide circuit breaker, if it is tripped.
+    /// Factories and tenders pause tender creation, bidding and escrow release while it is set.
+    pub circuit_breaker_reason: Option<String>,
+
+    /// The sequence of the events emitted by the contract.
+    pub events: EventLog,
+
+    /// The code hashes verified tenders were deployed with, as reported on verification.
+    pub tender_code_hashes: LookupMap<AccountId, Vec<u8>>,
+
+    /// The tenders whose verification is suspended because they no longer run the code they were verified with.
+    pub suspended_tenders: LookupSet<AccountId>,
+
+    /// The evidence the foundation attached to the verification of tenders and factories.
+    pub verification_metadata: LookupMap<AccountId, VersionedVerificationMetadata>,
+
+    /// The bounties paid to whoever reviews the pending verification requests of the given tenders, i.e. the
+    /// foundation or a delegated verifier.
+    pub review_bounties: LookupMap<AccountId, VersionedReviewBounty>,
+
+    /// The records of the verified factories, with their scope and activity.
+    pub factory_records: LookupMap<AccountId, VersionedFactoryRecord>,
+
+    /// The account IDs staged by the foundation to be imported from a legacy whitelist contract.
+    pub import_candidates: Vector<AccountId>,
+
+    /// The factories that verified the given tenders. Only the factory of a tender or the foundation can
+    /// verify it again or revalidate it.
+    pub tender_factories: LookupMap<AccountId, AccountId>,
+
+    /// The tenders waiting for the review of their verification request.
+    pub verification_requests: LookupSet<AccountId>,
+
+    /// The verifiers the foundation delegated the review of verification requests to.
+    pub verifiers: LookupSet<AccountId>,
+
+    /// The tenders reserved by their factory before deployment, waiting for `add_tender`.
+    pub reserved_tenders: LookupSet<AccountId>,
+}
+
+impl Default for VerifyTenderContract {
+    fn default() -> Self {
+        Error::NotInitialized.panic()
+    }
+}
+
+/// Stored layouts of the contract state. The state is stored without a version tag, so `migrate`
+/// reads it in the newest layout it matches: when the fields change, keep the previous layout as
+/// its own struct, add a variant holding it and convert it in `From`.
+pub enum VersionedState {
+    V1(VerifyTenderContract),
+}
+
+impl VersionedState {
+    /// Reads the stored state in the newest layout it matches.
+    fn read() -> Self {
+        let state = env::storage_read(b"STATE").unwrap_or_else(|| Error::NotInitialized.panic());
+        VerifyTenderContract::try_from_slice(&state)
+            .map(VersionedState::V1)
+            .unwrap_or_else(|_| Error::UnknownStateLayout.panic())
+    }
+}
+
+impl From<VersionedState> for VerifyTenderContract {
+    fn from(state: VersionedState) -> Self {
+        match state {
+            VersionedState::V1(state) => state,
+        }
+    }
+}
+
+#[near_bindgen]
+impl VerifyTenderContract {
+    /// Initializes the contract with the given Tender account ID.
+    #[init]
+    pub fn new(foundation_account_id: AccountId) -> Self {
+        require!(!env::state_exists(), Error::AlreadyInitialized);
+        require!(
+            env::is_valid_account_id(foundation_account_id.as_bytes()),
+            Error::InvalidFoundationAccountId
+        );
+        Self {
+            foundation: FoundationRole::new(foundation_account_id),
+            verified: LookupSet::new(StorageKey::VerifiedTenders),
+            factory_verified: LookupSet::new(StorageKey::VerifiedFactories),
+            supplier_verified: LookupSet::new(StorageKey::VerifiedSuppliers),
+            attesters: LookupMap::new(StorageKey::Attesters),
+            supplier_registry_account_id: Timelocked::new(None),
+            upgrade_coordinator_account_id: Timelocked::new(None),
+            timelock_delay: Timelocked::new(DEFAULT_TIMELOCK_DELAY),
+            circuit_breaker_reason: None,
+            events: EventLog::new(),
+            tender_code_hashes: LookupMap::new(StorageKey::TenderCodeHashes),
+            suspended_tenders: LookupSet::new(StorageKey::SuspendedTenders),
+            verification_metadata: LookupMap::new(StorageKey::VerificationMetadata),
+            review_bounties: LookupMap::new(StorageKey::ReviewBounties),
+            factory_records: LookupMap::new(StorageKey::FactoryRecords),
+            import_candidates: Vector::new(StorageKey::ImportCandidates),
+            tender_factories: LookupMap::new(StorageKey::TenderFactories),
+            verification_requests: LookupSet::new(StorageKey::VerificationRequests),
+            verifiers: LookupSet::new(StorageKey::TenderVerifiers),
+            reserved_tenders: LookupSet::new(StorageKey::ReservedTenders),
+        }
+    }
+
+    /// Migrates the stored state to the current layout, see `VersionedState`.
+    /// This method can only be called by the contract itself, right after `upgrade` deployed the new code.
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        assert_self();
+        VersionedState::read().into()
+    }
+
+
+
+    /// Returns the account ID of the Tenderbox foundation.
+    pub fn get_foundation_account_id(&self) -> AccountId {
+        self.foundation.account_id().clone()
+    }
+
+    /// Returns the sequence number of the last event emitted by the contract, `0` if none was emitted yet.
+    pub fn get_last_event_seq(&self) -> u64 {
+        self.events.last_seq()
+    }
+
+    /// Returns `true` if the given tender account ID is verified.
+    pub fn is_verified(&self, tender_account_id: AccountId) -> bool {
+        require!(env::is_valid_account_id(tender_account_id.as_bytes()), Error::InvalidAccountId);
+        self.verified.contains(&tender_account_id)
+    }
+
+    /// Returns the code hash the given tender was verified with, if it was reported.
+    pub fn get_tender_code_hash(&self, tender_account_id: AccountId) -> Option<Base64VecU8> {
+        self.tender_code_hashes.get(&tender_account_id).map(Base64VecU8::from)
+    }
+
+    /// Returns `true` if the verification of the given tender is suspended after a code change.
+    pub fn is_tender_suspended(&self, tender_account_id: AccountId) -> bool {
+        self.suspended_tenders.contains(&tender_account_id)
+    }
+
+    /// Returns the evidence attached to the verification of the given tender or factory, if any.
+    pub fn get_verification_metadata(&self, account_id: AccountId) -> Option<VerificationMetadata> {
+        self.verification_metadata.get(&account_id).map(VerificationMetadata::from)
+    }
+
+    /// Returns `true` if the given tender has a pending verification request.
+    pub fn is_verification_requested(&self, tender_account_id: AccountId) -> bool {
+        self.verification_requests.contains(&tender_account_id)
+    }
+
+    /// Returns `true` if the given account ID is a delegated verifier.
+    pub fn is_verifier(&self, verifier_account_id: AccountId) -> bool {
+        self.verifiers.contains(&verifier_account_id)
+    }
+
+    /// Returns the bounty paid to whoever reviews the verification request of the given tender, `0` if none was funded.
+    pub fn get_review_bounty(&self, tender_account_id: AccountId) -> U128 {
+        self.review_bounties
+            .get(&tender_account_id)
+            .map(|bounty| ReviewBounty::from(bounty).amount())
+            .unwrap_or(0)
+            .into()
+    }
+
+    /// Returns the record of the given verified factory, if it is verified.
+    pub fn get_factory_record(&self, factory_account_id: AccountId) -> Option<VerifiedFactoryView> {
+        self.factory_records
+            .get(&factory_account_id)
+            .map(FactoryRecord::from)
+            .map(|record| VerifiedFactoryView {
+                verified_at: record.verified_at.into(),
+                verified_by: record.verified_by,
+                industries: record.industries,
+                tender_quota: record.tender_quota.map(U64::from),
+                tenders_verified: record.tenders_verified.into(),
+            })
+    }
+
+    /// Returns `true` if the given factory contract account ID is whitelisted.
+    pub fn is_factory_verified(&self, factory_account_id: AccountId) -> bool {
+        require!(env::is_valid_account_id(factory_account_id.as_bytes()), Error::InvalidAccountId);
+        self.factory_verified.contains(&factory_account_id)
+    }
+
+    /// Returns `true` if the given supplier account ID is verified.
+    pub fn is_supplier_verified(&self, supplier_account_id: AccountId) -> bool {
+        require!(env::is_valid_account_id(supplier_account_id.as_bytes()), Error::InvalidAccountId);
+        self.supplier_verified.contains(&supplier_account_id)
+    }
+
+    /// Returns `true` if the given account ID is a registered KYC attester.
+    pub fn is_attester(&self, attester_account_id: AccountId) -> bool {
+        require!(env::is_valid_account_id(attester_account_id.as_bytes()), Error::InvalidAccountId);
+        self.attesters.contains_key(&attester_account_id)
+    }
+
+    /// Returns the public key the given KYC attester signs attestations with, if registered.
+    pub fn get_attester_public_key(&self, attester_account_id: AccountId) -> Option<Base58PublicKey> {
+        require!(env::is_valid_account_id(attester_account_id.as_bytes()), Error::InvalidAccountId);
+        self.attesters.get(&attester_account_id)
+    }
+
+    /// Returns `true` if the platform-wide circuit breaker is tripped.
+    pub fn is_circuit_breaker_tripped(&self) -> bool {
+        self.circuit_breaker_reason.is_some()
+    }
+
+    /// Returns the reason the circuit breaker was tripped, if it is tripped.
+    pub fn get_circuit_breaker_reason(&self) -> Option<String> {
+        self.circuit_breaker_reason.clone()
+    }
+
+    /// Returns the account ID of the upgrade coordinator contract, if set.
+    pub fn get_upgrade_coordinator(&self) -> Option<AccountId> {
+        self.upgrade_coordinator_account_id.get()
+    }
+
+    /// Returns the account ID of the supplier registry contract, if set.
+    pub fn get_supplier_registry(&self) -> Option<AccountId> {
+        self.supplier_registry_account_id.get()
+    }
+
+    /// Returns the announced change of the supplier registry contract that is not in effect yet, if any.
+    pub fn get_pending_supplier_registry(&self) -> Option<PendingChange<Option<AccountId>>> {
+        self.supplier_registry_account_id.pending()
+    }
+
+    /**********/
+    /* Anyone */
+    /**********/
+
+    /// Files a verification request for the predecessor, to be reviewed by the foundation or a delegated verifier.
+    /// Returns `true` if the tender had no pending request before, `false` otherwise.
+    pub fn request_verification(&mut self) -> bool {
+        let tender_account_id = env::predecessor_account_id();
+        require!(!self.verified.contains(&tender_account_id), Error::AlreadyVerified);
+        require!(!self.suspended_tenders.contains(&tender_account_id), Error::TenderSuspended);
+        if !self.verification_requests.insert(&tender_account_id) {
+            return false;
+        }
+        self.events.emit(format!("The tender @{} requested its verification", tender_account_id).as_bytes());
+        true
+    }
+
+    /// Adds the attached deposit to the bounty paid to whoever reviews the pending verification request of
+    /// the given tender.
+    /// Returns the new bounty.
+    #[payable]
+    pub fn fund_review(&mut self, tender_account_id: AccountId) -> U128 {
+        require!(env::is_valid_account_id(tender_account_id.as_bytes()), Error::InvalidAccountId);
+        require!(env::attached_deposit() > 0, Error::DepositNotPositive);
+        require!(!self.verified.contains(&tender_account_id), Error::AlreadyVerified);
+        require!(self.verification_requests.contains(&tender_account_id), Error::NoVerificationRequest);
+        let funder_account_id = env::predecessor_account_id();
+        let mut bounty = self.review_bounties.get(&tender_account_id).map(ReviewBounty::from).unwrap_or_default();
+        match bounty.funders.iter_mut().find(|(account_id, _)| *account_id == funder_account_id) {
+            Some((_, amount)) => *amount += env::attached_deposit(),
+            None => bounty.funders.push((funder_account_id, env::attached_deposit())),
+        }
+        let amount = bounty.amount();
+        self.review_bounties.insert(&tender_account_id, &bounty.into());
+        amount.into()
+    }
+
+    /// Sends the share of the predecessor in the bounty of the given tender back, as long as it was not paid out.
+    pub fn withdraw_review_bounty(&mut self, tender_account_id: AccountId) -> Promise {
+        let funder_account_id = env::predecessor_account_id();
+        let mut bounty = self
+            .review_bounties
+            .get(&tender_account_id)
+            .map(ReviewBounty::from)
+            .unwrap_or_else(|| Error::NoReviewBounty.panic());
+        let index = bounty
+            .funders
+            .iter()
+            .position(|(account_id, _)| *account_id == funder_account_id)
+            .unwrap_or_else(|| Error::NoReviewBounty.panic());
+        let (_, amount) = bounty.funders.swap_remove(index);
+        if bounty.funders.is_empty() {
+            self.review_bounties.remove(&tender_account_id);
+        } else {
+            self.review_bounties.insert(&tender_account_id, &bounty.into());
+        }
+        Promise::new(funder_account_id).transfer(amount)
+    }
+
+    /************************/
+    /* Tender Factory + Tenderbox Foundation */
+    /************************/
+
+    /// Reserves the verification of the given tender for the predecessor before it deploys the tender, checking
+    /// the given industry is within the scope of the factory and the factory didn't reach its quota.
+    /// The reserved tender counts against the quota, and only the reserving factory can verify it.
+    /// Returns `true` if the tender was not reserved before, `false` otherwise.
+    /// This method can only be called by a verified factory.
+    pub fn reserve_tender(&mut self, tender_account_id: AccountId, industry: String) -> bool {
+        require!(env::is_valid_account_id(tender_account_id.as_bytes()), Error::InvalidAccountId);
+        let factory_account_id = env::predecessor_account_id();
+        let mut record = self
+            .factory_records
+            .get(&factory_account_id)
+            .map(FactoryRecord::from)
+            .unwrap_or_else(|| Error::NotVerified.panic());
+        require!(
+            !self.verified.contains(&tender_account_id) && !self.suspended_tenders.contains(&tender_account_id),
+            Error::AlreadyVerified
+        );
+        if self.reserved_tenders.contains(&tender_account_id) {
+            self.assert_called_by_tender_factory_or_foundation(&tender_account_id);
+            return false;
+        }
+        require!(
+            record.industries.is_empty() || record.industries.contains(&industry),
+            Error::OutOfFactoryScope
+        );
+        if let Some(tender_quota) = record.tender_quota {
+            require!(record.tenders_verified < tender_quota, Error::FactoryQuotaReached);
+        }
+        record.tenders_verified += 1;
+        self.factory_records.insert(&factory_account_id, &record.into());
+        self.reserved_tenders.insert(&tender_account_id);
+        self.tender_factories.insert(&tender_account_id, &factory_account_id);
+        true
+    }
+
+    /// Releases the reservation of the given tender, e.g. after its deployment failed, giving the factory its
+    /// quota back.
+    /// Returns `true` if the tender was reserved before, `false` otherwise.
+    /// This method can be called either by the Tenderbox foundation or by the factory that reserved the tender.
+    pub fn release_tender(&mut self, tender_account_id: AccountId) -> bool {
+        if !self.reserved_tenders.contains(&tender_account_id) {
+            return false;
+        }
+        self.assert_called_by_tender_factory_or_foundation(&tender_account_id);
+        self.reserved_tenders.remove(&tender_account_id);
+        if let Some(factory_account_id) = self.tender_factories.remove(&tender_account_id) {
+            if let Some(mut record) = self.factory_records.get(&factory_account_id).map(FactoryRecord::from) {
+                record.tenders_verified = record.tenders_verified.saturating_sub(1);
+                self.factory_records.insert(&factory_account_id, &record.into());
+            }
+        }
+        true
+    }
+
+    /// Adds the given tender account ID to the verified list, recording the code hash it was deployed with if given.
+    /// A factory has to reserve the tender with `reserve_tender` first.
+    /// A suspended tender can only be verified again with `reinstate_tender`.
+    /// When the foundation verifies a tender with a pending verification request, it completes the review and
+    /// is paid its bounty. A factory verifying the tender leaves the bounty to its funders.
+    /// Returns `true` if the tender was not verified before, `false` otherwise.
+    /// This method can be called either by the Tenderbox foundation/company or by a verified factory. Once
+    /// verified, only the factory that verified the tender or the foundation can call it for the tender.
+    pub fn add_tender(&mut self, tender_account_id: AccountId, code_hash: Option<Base64VecU8>) -> bool {
+        require!(env::is_valid_account_id(tender_account_id.as_bytes()), Error::InvalidAccountId);
+        self.assert_called_by_factory_or_foundation();
+        if self.verified.contains(&tender_account_id) || self.tender_factories.get(&tender_account_id).is_some() {
+            self.assert_called_by_tender_factory_or_foundation(&tender_account_id);
+        }
+        require!(!self.suspended_tenders.contains(&tender_account_id), Error::TenderSuspended);
+        require!(
+            self.foundation.is(&env::predecessor_account_id())
+                || self.verified.contains(&tender_account_id)
+                || self.reserved_tenders.contains(&tender_account_id),
+            Error::NoTenderReservation
+        );
+        if let Some(code_hash) = code_hash {
+            require!(code_hash.0.len() == 32, Error::InvalidCodeHash);
+            match self.tender_code_hashes.get(&tender_account_id) {
+                Some(verified_code_hash) => require!(verified_code_hash == code_hash.0, Error::CodeHashMismatch),
+                None => {
+                    self.tender_code_hashes.insert(&tender_account_id, &code_hash.0);
+                }
+            }
+        }
+        self.reserved_tenders.remove(&tender_account_id);
+        if self.verification_requests.remove(&tender_account_id)
+            && self.foundation.is(&env::predecessor_account_id())
+        {
+            self.internal_pay_review_bounty(&tender_account_id);
+        }
+        self.verified.insert(&tender_account_id)
+    }
+
+    /// Checks the given code hash, reported as the one the given verified tender currently runs, against
+    /// the code hash it was verified with. Records it if none was reported on verification.
+    /// Suspends the verification of the tender when the code changed.
+    /// Returns `true` if the tender is still verified, `false` otherwise.
+    /// This method can be called either by the Tenderbox foundation/company or by the factory that verified the tender.
+    pub fn revalidate_tender(&mut self, tender_account_id: AccountId, code_hash: Base64VecU8) -> bool {
+        require!(env::is_valid_account_id(tender_account_id.as_bytes()), Error::InvalidAccountId);
+        self.assert_called_by_tender_factory_or_foundation(&tender_account_id);
+        require!(code_hash.0.len() == 32, Error::InvalidCodeHash);
+        if !self.verified.contains(&tender_account_id) {
+            return false;
+        }
+        match self.tender_code_hashes.get(&tender_account_id) {
+            Some(verified_code_hash) if verified_code_hash != code_hash.0 => {
+                self.verified.remove(&tender_account_id);
+                self.suspended_tenders.insert(&tender_account_id);
+                self.events.emit(
+                    format!(
+                        "The verification of the tender @{} is suspended: its code changed",
+                        tender_account_id
+                    )
+                    .as_bytes(),
+                );
+                false
+            }
+            Some(_) => true,
+            None => {
+                self.tender_code_hashes.insert(&tender_account_id, &code_hash.0);
+                true
+            }
+        }
+    }
+
+    /*****************************************/
+    /* Tenderbox Foundation + Verifiers */
+    /*****************************************/
+
+    /// Completes the review of the pending verification request of the given tender, verifying it if `approved`.
+    /// The review bounty of the tender is paid to the predecessor either way.
+    /// Returns `true` if the tender was verified by the review, `false` otherwise.
+    /// This method can be called either by the Tenderbox foundation or by a delegated verifier.
+    pub fn review_tender(&mut self, tender_account_id: AccountId, approved: bool) -> bool {
+        let reviewer_account_id = env::predecessor_account_id();
+        require!(
+            self.foundation.is(&reviewer_account_id) || self.verifiers.contains(&reviewer_account_id),
+            Error::NotFoundationOrVerifier
+        );
+        require!(self.verification_requests.remove(&tender_account_id), Error::NoVerificationRequest);
+        self.internal_pay_review_bounty(&tender_account_id);
+        self.events.emit(
+            format!(
+                "The verification request of the tender @{} was {} by @{}",
+                tender_account_id,
+                if approved { "approved" } else { "rejected" },
+                reviewer_account_id
+            )
+            .as_bytes(),
+        );
+        approved && self.verified.insert(&tender_account_id)
+    }
+
+    /**************/
+    /* Tenderbox Foundation */
+    /**************/
+
+    /// Proposes the given account ID, e.g. a Sputnik DAO, as the new Tenderbox foundation account.
+    /// The role is only handed over once the proposed account calls `accept_foundation_role`,
+    /// which a DAO does through a function-call proposal.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn propose_foundation_account_id(&mut self, foundation_account_id: AccountId) {
+        self.assert_called_by_foundation();
+        self.foundation.propose(foundation_account_id);
+    }
+
+    /// Accepts the Tenderbox foundation role proposed to the predecessor.
+    /// This method can only be called by the account proposed with `propose_foundation_account_id`.
+    pub fn accept_foundation_role(&mut self) {
+        self.foundation.accept();
+        self.events.emit(
+            format!(
+                "The Tenderbox foundation role was handed over to @{}",
+                self.foundation.account_id()
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Stages the given tender account IDs to be imported from a legacy whitelist contract by `import_from`.
+    /// The legacy contract can't enumerate its entries, so the foundation passes the candidates first.
+    /// Returns the number of staged candidates.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn add_import_candidates(&mut self, account_ids: Vec<AccountId>) -> u64 {
+        self.assert_called_by_foundation();
+        for account_id in account_ids.iter() {
+            require!(env::is_valid_account_id(account_id.as_bytes()), Error::InvalidAccountId);
+        }
+        self.import_candidates.extend(account_ids);
+        self.import_candidates.len()
+    }
+
+    /// Drops the staged import candidates, e.g. once the migration is over.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn clear_import_candidates(&mut self) {
+        self.assert_called_by_foundation();
+        self.import_candidates.clear();
+    }
+
+    /// Returns the number of staged import candidates.
+    pub fn get_import_candidates_len(&self) -> u64 {
+        self.import_candidates.len()
+    }
+
+    /// Imports up to `limit` staged candidates starting at `from` into the verified list, each once the given
+    /// legacy whitelist contract confirms it is whitelisted there. `limit` is capped at `MAX_IMPORT_BATCH`
+    /// so the attached gas stays in bounds.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn import_from(&mut self, legacy_account_id: AccountId, from: u64, limit: u64) -> Promise {
+        self.assert_called_by_foundation();
+        require!(env::is_valid_account_id(legacy_account_id.as_bytes()), Error::InvalidAccountId);
+        require!(
+            limit > 0 && limit <= MAX_IMPORT_BATCH as u64,
+            Error::ImportBatchSize(MAX_IMPORT_BATCH)
+        );
+        let to = std::cmp::min(from.saturating_add(limit), self.import_candidates.len());
+        require!(from < to, Error::ImportBatchSize(MAX_IMPORT_BATCH));
+        (from..to)
+            .map(|index| {
+                let account_id = self.import_candidates.get(index).unwrap();
+                ext_legacy_whitelist::is_whitelisted(
+                    account_id.clone(),
+                    &legacy_account_id,
+                    NO_DEPOSIT,
+                    gas::LEGACY_WHITELIST,
+                )
+                .then(ext_self::on_legacy_entry_checked(
+                    account_id,
+                    legacy_account_id.clone(),
+                    &env::current_account_id(),
+                    NO_DEPOSIT,
+                    gas::IMPORT_CALLBACK,
+                ))
+            })
+            .reduce(|batch, promise| batch.and(promise))
+            .unwrap()
+    }
+
+    /// Callback after the legacy whitelist contract was asked about the given tender.
+    /// Returns `true` if the tender is whitelisted there and was not verified before, `false` otherwise.
+    pub fn on_legacy_entry_checked(
+        &mut self,
+        tender_account_id: AccountId,
+        legacy_account_id: AccountId,
+    ) -> bool {
+        assert_self();
+        let whitelisted = promise_result_as_bool();
+        if !whitelisted || !self.verified.insert(&tender_account_id) {
+            return false;
+        }
+        self.events.emit(
+            format!(
+                "The tender @{} was imported from the legacy whitelist @{}",
+                tender_account_id, legacy_account_id
+            )
+            .as_bytes(),
+        );
+        true
+    }
+
+    /// Lifts the suspension of the given tender once the foundation reviewed the code it now runs, recording
+    /// the given code hash as the verified one.
+    /// Returns `true` if the tender was suspended before, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn reinstate_tender(&mut self, tender_account_id: AccountId, code_hash: Base64VecU8) -> bool {
+        self.assert_called_by_foundation();
+        require!(code_hash.0.len() == 32, Error::InvalidCodeHash);
+        if !self.suspended_tenders.remove(&tender_account_id) {
+            return false;
+        }
+        self.tender_code_hashes.insert(&tender_account_id, &code_hash.0);
+        self.verified.insert(&tender_account_id);
+        self.events.emit(format!("The verification of the tender @{} is reinstated", tender_account_id).as_bytes());
+        true
+    }
+
+    /// Removes the given tender account ID from the list of verified tenders(verified).
+    /// Returns `true` if the tender was present in the verified tenders' list before, `false` otherwise.
+    /// This method can only be called by Tenderbox Foundation(Guardian company.
+    pub fn remove_tender(&mut self, tender_account_id: AccountId) -> bool {
+        self.assert_called_by_foundation();
+        require!(env::is_valid_account_id(tender_account_id.as_bytes()), Error::InvalidAccountId);
+        self.suspended_tenders.remove(&tender_account_id);
+        self.tender_code_hashes.remove(&tender_account_id);
+        self.tender_factories.remove(&tender_account_id);
+        self.verification_metadata.remove(&tender_account_id);
+        self.verified.remove(&tender_account_id)
+    }
+
+    /// Adds the given tender factory contract account ID to the list of verified Tender Factories.
+    /// Returns `true` if the factory was not in the verified list before, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn add_factory(&mut self, factory_account_id: AccountId) -> bool {
+        require!(env::is_valid_account_id(factory_account_id.as_bytes()), Error::InvalidAccountId);
+        self.assert_called_by_foundation();
+        if !self.factory_verified.insert(&factory_account_id) {
+            return false;
+        }
+        self.factory_records.insert(
+            &factory_account_id,
+            &FactoryRecord {
+                verified_at: env::block_timestamp(),
+                verified_by: env::predecessor_account_id(),
+                industries: vec![],
+                tender_quota: None,
+                tenders_verified: 0,
+            }
+            .into(),
+        );
+        true
+    }
+
+    /// Sets the industries the given verified factory is meant to issue tenders for and the maximum
+    /// number of tenders it can verify, `None` for no limit.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn set_factory_scope(
+        &mut self,
+        factory_account_id: AccountId,
+        industries: Vec<String>,
+        tender_quota: Option<U64>,
+    ) {
+        self.assert_called_by_foundation();
+        let mut record = self
+            .factory_records
+            .get(&factory_account_id)
+            .map(FactoryRecord::from)
+            .unwrap_or_else(|| Error::NotVerified.panic());
+        record.industries = industries;
+        record.tender_quota = tender_quota.map(|quota| quota.0);
+        self.factory_records.insert(&factory_account_id, &record.into());
+        self.events.emit(format!("The scope of the factory @{} changed", factory_account_id).as_bytes());
+    }
+
+    /// Delegates the review of verification requests to the given account ID.
+    /// Returns `true` if it was not a verifier before, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn add_verifier(&mut self, verifier_account_id: AccountId) -> bool {
+        self.assert_called_by_foundation();
+        require!(env::is_valid_account_id(verifier_account_id.as_bytes()), Error::InvalidAccountId);
+        self.verifiers.insert(&verifier_account_id)
+    }
+
+    /// Revokes the delegation of the review of verification requests to the given account ID.
+    /// Returns `true` if it was a verifier before, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn remove_verifier(&mut self, verifier_account_id: AccountId) -> bool {
+        self.assert_called_by_foundation();
+        self.verifiers.remove(&verifier_account_id)
+    }
+
+    /// Removes the given tender factory account ID from the list of verified factories.
+    /// Returns `true` if the factory was present in the list of verified factories before, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn remove_factory(&mut self, factory_account_id: AccountId) -> bool {
+        self.assert_called_by_foundation();
+        require!(env::is_valid_account_id(factory_account_id.as_bytes()), Error::InvalidAccountId);
+        self.verification_metadata.remove(&factory_account_id);
+        self.factory_records.remove(&factory_account_id);
+        self.factory_verified.remove(&factory_account_id)
+    }
+
+    /// Attaches the given evidence to the verification of the given verified tender or factory, replacing
+    /// any previous one.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn set_verification_metadata(&mut self, account_id: AccountId, metadata: VerificationMetadata) {
+        self.assert_called_by_foundation();
+        require!(
+            self.verified.contains(&account_id) || self.factory_verified.contains(&account_id),
+            Error::NotVerified
+        );
+        require!(!metadata.url.is_empty(), Error::EmptyUrl);
+        require!(metadata.content_hash.0.len() == 32, Error::InvalidContentHash);
+        self.events.emit(
+            format!("The verification metadata of @{} is set to {}", account_id, metadata.url).as_bytes(),
+        );
+        self.verification_metadata.insert(&account_id, &metadata.into());
+    }
+
+    /// Adds the given supplier account ID to the list of verified suppliers.
+    /// Returns `true` if the supplier was not in the verified list before, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn add_supplier(&mut self, supplier_account_id: AccountId) -> bool {
+        require!(env::is_valid_account_id(supplier_account_id.as_bytes()), Error::InvalidAccountId);
+        self.assert_called_by_foundation();
+        self.supplier_verified.insert(&supplier_account_id)
+    }
+
+    /// Removes the given supplier account ID from the list of verified suppliers.
+    /// Returns `true` if the supplier was present in the list of verified suppliers before, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn remove_supplier(&mut self, supplier_account_id: AccountId) -> bool {
+        self.assert_called_by_foundation();
+        require!(env::is_valid_account_id(supplier_account_id.as_bytes()), Error::InvalidAccountId);
+        self.supplier_verified.remove(&supplier_account_id)
+    }
+
+    /// Registers the given account ID as a KYC attester signing with the given public key.
+    /// Returns `true` if the attester was not registered before, `false` if only its key was replaced.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn add_attester(
+        &mut self,
+        attester_account_id: AccountId,
+        attester_public_key: Base58PublicKey,
+    ) -> bool {
+        require!(env::is_valid_account_id(attester_account_id.as_bytes()), Error::InvalidAccountId);
+        self.assert_called_by_foundation();
+        self.attesters
+            .insert(&attester_account_id, &attester_public_key)
+            .is_none()
+    }
+
+    /// Removes the given account ID from the registry of KYC attesters.
+    /// Returns `true` if the attester was registered before, `false` otherwise.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn remove_attester(&mut self, attester_account_id: AccountId) -> bool {
+        self.assert_called_by_foundation();
+        require!(env::is_valid_account_id(attester_account_id.as_bytes()), Error::InvalidAccountId);
+        self.attesters.remove(&attester_account_id).is_some()
+    }
+
+    /// Announces the account ID of the supplier registry contract that tenders use for prequalification checks,
+    /// in effect after the timelock delay.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn set_supplier_registry(&mut self, supplier_registry_account_id: AccountId) {
+        self.assert_called_by_foundation();
+        require!(
+            env::is_valid_account_id(supplier_registry_account_id.as_bytes()),
+            Error::InvalidAccountId
+        );
+        let effective_at = self.supplier_registry_account_id.schedule(
+            Some(supplier_registry_account_id.clone()),
+            self.timelock_delay.get(),
+        );
+        self.events.emit(
+            format!(
+                "The supplier registry changes to @{} at {}",
+                supplier_registry_account_id, effective_at
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Announces the account ID of the upgrade coordinator contract, in effect after the timelock delay.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn set_upgrade_coordinator(&mut self, upgrade_coordinator_account_id: AccountId) {
+        self.assert_called_by_foundation();
+        require!(
+            env::is_valid_account_id(upgrade_coordinator_account_id.as_bytes()),
+            Error::InvalidAccountId
+        );
+        let effective_at = self.upgrade_coordinator_account_id.schedule(
+            Some(upgrade_coordinator_account_id.clone()),
+            self.timelock_delay.get(),
+        );
+        self.events.emit(
+            format!(
+                "The upgrade coordinator changes to @{} at {}",
+                upgrade_coordinator_account_id, effective_at
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Announces a new timelock delay in nanoseconds, in effect after the current timelock delay.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn set_timelock_delay(&mut self, timelock_delay: U64) {
+        self.assert_called_by_foundation();
+        let effective_at = self
+            .timelock_delay
+            .schedule(timelock_delay.0, self.timelock_delay.get());
+        self.events.emit(
+            format!(
+                "The timelock delay changes to {} at {}",
+                timelock_delay.0, effective_at
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Trips the platform-wide circuit breaker with the given reason, e.g. a discovered vulnerability in the tender wasm.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn trip_circuit_breaker(&mut self, reason: String) {
+        self.assert_called_by_foundation();
+        require!(!reason.is_empty(), Error::EmptyReason);
+        self.events.emit(format!("The circuit breaker is tripped: {}", reason).as_bytes());
+        self.circuit_breaker_reason = Some(reason);
+    }
+
+    /// Resets the platform-wide circuit breaker.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn reset_circuit_breaker(&mut self) {
+        self.assert_called_by_foundation();
+        self.events.emit(b"The circuit breaker is reset");
+        self.circuit_breaker_reason = None;
+    }
+
+    /// Cancels the configuration changes that are not in effect yet.
+    /// This method can only be called by the Tenderbox foundation.
+    pub fn cancel_pending_config(&mut self) {
+        self.assert_called_by_foundation();
+        self.supplier_registry_account_id.cancel();
+        self.upgrade_coordinator_account_id.cancel();
+        self.timelock_delay.cancel();
+        self.events.emit(b"The pending configuration changes are cancelled");
+    }
+
+    /// Deploys the given code on the contract. The upgrade coordinator checks the code against the
+    /// versions approved by the foundation.
+    /// This method can only be called by the upgrade coordinator.
+    pub fn upgrade(&mut self, code: Base64VecU8) -> Promise {
+        require!(
+            Some(env::predecessor_account_id()) == self.upgrade_coordinator_account_id.get(),
+            Error::NotUpgradeCoordinator
+        );
+        self.events.emit(b"Upgrading the verify tender code");
+        Promise::new(env::current_account_id())
+            .deploy_contract(code.0)
+            .function_call(b"migrate".to_vec(), vec![], NO_DEPOSIT, gas::MIGRATE)
+    }
+
+    /************/
+    /* Internal */
+    /************/
+
+    /// Internal method to verify the predecessor was the Tenderbox Foundation account ID.
+    fn assert_called_by_foundation(&self) {
+        self.foundation.assert_called_by();
+    }
+
+    /// Internal method paying the review bounty of the given tender, if any, to the predecessor.
+    fn internal_pay_review_bounty(&mut self, tender_account_id: &AccountId) {
+        if let Some(bounty) = self.review_bounties.remove(tender_account_id).map(ReviewBounty::from) {
+            let reviewer_account_id = env::predecessor_account_id();
+            self.events.emit(
+                format!(
+                    "The review bounty of {} for the tender @{} is paid to @{}",
+                    bounty.amount(),
+                    tender_account_id,
+                    reviewer_account_id
+                )
+                .as_bytes(),
+            );
+            Promise::new(reviewer_account_id).transfer(bounty.amount());
+        }
+    }
+
+    /// Internal method to verify the predecessor was a verified factory or the Tenderbox Foundation account ID.
+    fn assert_called_by_factory_or_foundation(&self) {
+        if !self
+            .factory_verified
+            .contains(&env::predecessor_account_id())
+        {
+            self.assert_called_by_foundation();
+        }
+    }
+
+    /// Internal method to verify the predecessor was the verified factory that verified the given tender
+    /// or the Tenderbox Foundation account ID.
+    fn assert_called_by_tender_factory_or_foundation(&self, tender_account_id: &AccountId) {
+        let predecessor_account_id = env::predecessor_account_id();
+        if !self.foundation.is(&predecessor_account_id) {
+            require!(
+                self.factory_verified.contains(&predecessor_account_id)
+                    && self.tender_factories.get(tender_account_id) == Some(predecessor_account_id),
+                Error::NotTenderFactory
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::{testing_env, MockedBlockchain, PromiseResult};
+
+    mod test_utils;
+    use std::convert::TryInto;
+    use test_utils::*;
+
+    #[test]
+    fn test_verified() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+
+        // Check initial list of verified tenders
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(!contract.is_verified(account_tender()));
+
+        // Adding to verified list by foundation
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.add_tender(account_tender(), None));
+
+        // Checking it's verified now
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.is_verified(account_tender()));
+
+        // Adding again. Should return false
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(!contract.add_tender(account_tender(), None));
+
+        // Checking the pool is still verified
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.is_verified(account_tender()));
+
+        // Removing from the list of verified tenders(called verified).
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.remove_tender(account_tender()));
+
+        // Checking the pool is not verified anymore
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(!contract.is_verified(account_tender()));
+
+        // Removing again from the whitelist, should return false.
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(!contract.remove_tender(account_tender()));
+
+        // Checking the pool is still not verified
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(!contract.is_verified(account_tender()));
+
+        // Adding again after it was removed. Should return true
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.add_tender(account_tender(), None));
+
+        // Checking the pool is now verified again
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.is_verified(account_tender()));
+    }
+
+    #[test]
+    #[should_panic(expected = "E101: Can only be called by the Tenderbox Foundation")]
+    fn test_factory_verified_fail() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+
+        // Trying ot add to the verified list by NOT verified factory.
+        context.is_view = false;
+        context.predecessor_account_id = account_factory();
+        testing_env!(context.clone());
+        assert!(contract.add_tender(account_tender(), None));
+    }
+
+    #[test]
+    #[should_panic(expected = "E101: Can only be called by the Tenderbox Foundation")]
+    fn test_trying_to_verify_factory() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+
+        // Trying to verify the factory not initiated by the Tenderbox Foundation.
+        context.is_view = false;
+        context.predecessor_account_id = account_tenderfactory();
+        testing_env!(context.clone());
+        assert!(contract.add_factory(account_tenderfactory()));
+    }
+
+    #[test]
+    #[should_panic(expected = "E101: Can only be called by the Tenderbox Foundation")]
+    fn test_trying_to_remove_by_factory() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+
+        // Adding factory
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.add_factory(account_factory()));
+
+        // Trying to remove the tender by the factory.
+        context.predecessor_account_id = account_factory();
+        testing_env!(context.clone());
+        assert!(contract.remove_tender(account_tender()));
+    }
+
+    #[test]
+    fn test_verified_factory() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+
+        // Check the factory is not verified
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(!contract.is_factory_verified(account_factory()));
+
+        // Verified factory
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.add_factory(account_factory()));
+
+        // Check the factory is verified now(whitelisted)
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.is_factory_verified(account_factory()));
+        // Check the tender is not verified
+        assert!(!contract.is_verified(account_tender()));
+
+        // Adding to list of verified tenders by foundation
+        context.is_view = false;
+        context.predecessor_account_id = account_factory();
+        testing_env!(context.clone());
+        contract.reserve_tender(account_tender(), "Furniture".to_string());
+        assert!(contract.add_tender(account_tender(), None));
+
+        // Checking it's verified now
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.is_verified(account_tender()));
+
+        // Removing the tender from the list of verified tenders by the Tenderbox foundation.
+        context.is_view = false;
+        context.predecessor_account_id = account_tenderbox();
+        testing_env!(context.clone());
+        assert!(contract.remove_tender(account_tender()));
+
+        // Checking the tender is not verified anymore
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(!contract.is_verified(account_tender()));
+
+        // Removing the factory
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.remove_factory(account_factory()));
+
+        // Check the factory is not verified anymore
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(!contract.is_factory_verified(account_factory()));
+    }
+
+    #[test]
+    fn test_verified_supplier() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+
+        // Check the supplier is not verified
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(!contract.is_supplier_verified(account_supplier()));
+
+        // Verifying the supplier by the foundation
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.add_supplier(account_supplier()));
+
+        // Adding again. Should return false
+        assert!(!contract.add_supplier(account_supplier()));
+
+        // Checking it's verified now
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.is_supplier_verified(account_supplier()));
+
+        // Removing the supplier
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.remove_supplier(account_supplier()));
+
+        // Check the supplier is not verified anymore
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(!contract.is_supplier_verified(account_supplier()));
+    }
+
+    #[test]
+    #[should_panic(expected = "E101: Can only be called by the Tenderbox Foundation")]
+    fn test_trying_to_verify_supplier_by_factory() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+
+        // Adding factory
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.add_factory(account_factory()));
+
+        // Trying to verify the supplier by the factory.
+        context.predecessor_account_id = account_factory();
+        testing_env!(context.clone());
+        assert!(contract.add_supplier(account_supplier()));
+    }
+
+    #[test]
+    fn test_attester_registry() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        let attester_public_key: Base58PublicKey = "KuTCtARNzxZQ3YvXDeLjx83FDqxv2SdQTSbiq876zR7"
+            .try_into()
+            .unwrap();
+
+        // Check the attester is not registered
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(!contract.is_attester(account_attester()));
+        assert!(contract.get_attester_public_key(account_attester()).is_none());
+
+        // Registering the attester by the foundation
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.add_attester(account_attester(), attester_public_key.clone()));
+
+        // Checking it's registered with its key now
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.is_attester(account_attester()));
+        assert_eq!(
+            contract.get_attester_public_key(account_attester()),
+            Some(attester_public_key)
+        );
+
+        // Removing the attester
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.remove_attester(account_attester()));
+        assert!(!contract.remove_attester(account_attester()));
+
+        // Check the attester is not registered anymore
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(!contract.is_attester(account_attester()));
+    }
+
+    #[test]
+    fn test_foundation_handover_to_dao() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+
+        // Proposing the DAO as the new foundation account
+        contract.propose_foundation_account_id(account_dao());
+
+        // The DAO accepts through a function-call proposal
+        context.predecessor_account_id = account_dao();
+        testing_env!(context.clone());
+        contract.accept_foundation_role();
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert_eq!(contract.get_foundation_account_id(), account_dao());
+
+        // The DAO can now verify factories
+        context.is_view = false;
+        testing_env!(context.clone());
+        assert!(contract.add_factory(account_factory()));
+    }
+
+    #[test]
+    #[should_panic(expected = "E102: Can only be called by the proposed Tenderbox Foundation account")]
+    fn test_foundation_role_not_proposed() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+
+        context.predecessor_account_id = account_dao();
+        testing_env!(context.clone());
+        contract.accept_foundation_role();
+    }
+
+    #[test]
+    fn test_circuit_breaker() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        assert!(!contract.is_circuit_breaker_tripped());
+
+        // Tripping by the foundation
+        contract.trip_circuit_breaker("Vulnerability in the tender wasm".to_string());
+
+        context.is_view = true;
+        testing_env!(context.clone());
+        assert!(contract.is_circuit_breaker_tripped());
+        assert_eq!(
+            contract.get_circuit_breaker_reason(),
+            Some("Vulnerability in the tender wasm".to_string())
+        );
+
+        // Resetting
+        context.is_view = false;
+        testing_env!(context.clone());
+        contract.reset_circuit_breaker();
+        assert!(!contract.is_circuit_breaker_tripped());
+    }
+
+    #[test]
+    fn test_migrate() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        assert!(contract.add_tender(account_tender(), None));
+        env::state_write(&contract);
+
+        context.predecessor_account_id = account_verified();
+        testing_env!(context.clone());
+        let contract = VerifyTenderContract::migrate();
+        assert_eq!(contract.get_foundation_account_id(), account_tenderbox());
+        assert!(contract.is_verified(account_tender()));
+    }
+
+    #[test]
+    #[should_panic(expected = "E103: Can only be called by the contract itself")]
+    fn test_migrate_not_by_self() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let contract = VerifyTenderContract::new(account_tenderbox());
+        env::state_write(&contract);
+        VerifyTenderContract::migrate();
+    }
+
+    #[test]
+    #[should_panic(expected = "E107: Can only be called by the upgrade coordinator")]
+    fn test_upgrade_not_by_coordinator() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        contract.set_upgrade_coordinator(account_upgrade_coordinator());
+
+        // The coordinator is only in effect after the timelock delay
+        context.predecessor_account_id = account_upgrade_coordinator();
+        testing_env!(context.clone());
+        contract.upgrade(vec![0u8; 16].into());
+    }
+
+    #[test]
+    fn test_revalidate_tender() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        contract.add_factory(account_factory());
 
-    /// The verified account IDs of approved Tender contracts.
-    pub verified: LookupSet<AccountId>,
+        // The factory verifies the tender with the hash of the code it deployed
+        context.predecessor_account_id = account_factory();
+        testing_env!(context.clone());
+        let code_hash: Base64VecU8 = env::sha256(b"tender code").into();
+        contract.reserve_tender(account_tender(), "Furniture".to_string());
+        assert!(contract.add_tender(account_tender(), Some(code_hash.clone())));
+        assert!(contract.revalidate_tender(account_tender(), code_hash.clone()));
 
-    /// The verified list of Tender factories. Any account from this lis   ///t can verify tenders.
-    pub factory_verified: LookupSet<AccountId>,
-}
+        // A tender running other code is suspended
+        assert!(!contract.revalidate_tender(account_tender(), env::sha256(b"other code").into()));
+        assert!(!contract.is_verified(account_tender()));
+        assert!(contract.is_tender_suspended(account_tender()));
 
-impl Default for VerifyTenderContract {
-    fn default() -> Self {
-        env::panic(b"The contract should be initialized before usage")
+        // Only the foundation lifts the suspension, once it reviewed the new code
+        context.predecessor_account_id = account_tenderbox();
+        testing_env!(context.clone());
+        let new_code_hash: Base64VecU8 = env::sha256(b"other code").into();
+        assert!(contract.reinstate_tender(account_tender(), new_code_hash.clone()));
+        assert!(contract.is_verified(account_tender()));
+        assert!(!contract.is_tender_suspended(account_tender()));
+        assert_eq!(contract.get_tender_code_hash(account_tender()), Some(new_code_hash));
     }
-}
 
-#[near_bindgen]
-impl VerifyTenderContract {
-    /// Initializes the contract with the given Tender account ID.
-    #[init]
-    pub fn new(foundation_account_id: AccountId) -> Self {
-        assert!(!env::state_exists(), "Already initialized");
-        assert!(
-            env::is_valid_account_id(foundation_account_id.as_bytes()),
-            "The Tenderbox account ID is invalid"
-        );
-        Self {
-            foundation_account_id,
-            verified: LookupSet::new(b"w".to_vec()),
-            factory_verified: LookupSet::new(b"f".to_vec()),
-        }
-    }
+    #[test]
+    #[should_panic(expected = "E546: The verification of the tender is suspended")]
+    fn test_add_suspended_tender() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
 
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        contract.add_factory(account_factory());
 
+        context.predecessor_account_id = account_factory();
+        testing_env!(context.clone());
+        let code_hash: Base64VecU8 = env::sha256(b"tender code").into();
+        contract.reserve_tender(account_tender(), "Furniture".to_string());
+        contract.add_tender(account_tender(), Some(code_hash.clone()));
+        contract.revalidate_tender(account_tender(), env::sha256(b"other code").into());
 
-    /// Returns `true` if the given tender account ID is verified.
-    pub fn is_verified(&self, staking_pool_account_id: AccountId) -> bool {
-        assert!(
-            env::is_valid_account_id(tender_account_id.as_bytes()),
-            "The given account ID is invalid"
-        );
-        self.verified.contains(&tender_account_id)
+        // Verifying it again doesn't lift the suspension
+        contract.add_tender(account_tender(), Some(code_hash));
     }
 
-    /// Returns `true` if the given factory contract account ID is whitelisted.
-    pub fn is_factory_verified(&self, factory_account_id: AccountId) -> bool {
-        assert!(
-            env::is_valid_account_id(factory_account_id.as_bytes()),
-            "The given account ID is invalid"
-        );
-        self.factory_verified.contains(&factory_account_id)
-    }
+    #[test]
+    #[should_panic(
+        expected = "E111: Can only be called by the factory that created the tender or the Tenderbox foundation"
+    )]
+    fn test_revalidate_tender_of_other_factory() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
 
-    /************************/
-    /* Tender Factory + Tenderbox Foundation */
-    /************************/
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        contract.add_factory(account_factory());
+        contract.add_factory(account_tenderfactory());
 
-    /// Adds the given tender account ID to the verified list.
-    /// Returns `true` if the tender was not verified before, `false` otherwise.
-    /// This method can be called either by the Tenderbox foundation/company or by a verified factory.
-    pub fn add_tender(&mut self, tender_account_id: AccountId) -> bool {
-        assert!(
-            env::is_valid_account_id(tender_account_id.as_bytes()),
-            "The given account ID is invalid"
-        );
-        // Can only be called by a verified factory or by the foundation.
-        if !self
-            .factory_verified
-            .contains(&env::predecessor_account_id())
-        {
+        context.predecessor_account_id = account_factory();
+        testing_env!(context.clone());
+        contract.reserve_tender(account_tender(), "Furniture".to_string());
+        contract.add_tender(account_tender(), Some(env::sha256(b"tender code").into()));
 
-	     self.assert_called_by_foundation();
-        }
-        self.verified.insert(&tender_account_id)
+        context.predecessor_account_id = account_tenderfactory();
+        testing_env!(context.clone());
+        contract.revalidate_tender(account_tender(), env::sha256(b"other code").into());
     }
 
-    /**************/
-    /* Tenderbox Foundation */
-    /**************/
+    #[test]
+    fn test_verification_metadata() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
 
-    /// Removes the given tender account ID from the list of verified tenders(verified).
-    /// Returns `true` if the tender was present in the verified tenders' list before, `false` otherwise.
-    /// This method can only be called by Tenderbox Foundation(Guardian company.
-    pub fn remove_tender(&mut self, staking_pool_account_id: AccountId) -> bool {
-        self.assert_called_by_foundation();
-        assert!(
-            env::is_valid_account_id(tender_account_id.as_bytes()),
-            "The given account ID is invalid"
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        contract.add_factory(account_factory());
+        contract.set_verification_metadata(
+            account_factory(),
+            VerificationMetadata {
+                url: "https://tenderbox.example/audits/factory.pdf".to_string(),
+                content_hash: env::sha256(b"audit report").into(),
+            },
         );
-        self.verified.remove(&tender_account_id)
-    }
-
-    /// Adds the given tender factory contract account ID to the list of verified Tender Factories.
-    /// Returns `true` if the factory was not in the verified list before, `false` otherwise.
-    /// This method can only be called by the Tenderbox foundation.
-    pub fn add_factory(&mut self, factory_account_id: AccountId) -> bool {
-        assert!(
-            env::is_valid_account_id(factory_account_id.as_bytes()),
-            "The given account ID is invalid"
+        assert_eq!(
+            contract.get_verification_metadata(account_factory()).unwrap().url,
+            "https://tenderbox.example/audits/factory.pdf"
         );
-        self.assert_called_by_foundation();
-        self.factory_whitelist.insert(&factory_account_id)
+
+        // Removing the factory drops its evidence
+        contract.remove_factory(account_factory());
+        assert!(contract.get_verification_metadata(account_factory()).is_none());
     }
 
-    /// Removes the given tender factory account ID from the list of verified factories.
-    /// Returns `true` if the factory was present in the list of verified factories before, `false` otherwise.
-    /// This method can only be called by the Tenderbox foundation.
-    pub fn remove_factory(&mut self, factory_account_id: AccountId) -> bool {
-        self.assert_called_by_foundation();
-        assert!(
-            env::is_valid_account_id(factory_account_id.as_bytes()),
-            "The given account ID is invalid"
+    #[test]
+    #[should_panic(expected = "E537: The account is neither a verified tender nor a verified factory")]
+    fn test_verification_metadata_not_verified() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        contract.set_verification_metadata(
+            account_tender(),
+            VerificationMetadata {
+                url: "https://tenderbox.example/audits/tender.pdf".to_string(),
+                content_hash: env::sha256(b"audit report").into(),
+            },
         );
-        self.factory_verified.remove(&factory_account_id)
     }
 
-    /************/
-    /* Internal */
-    /************/
+    #[test]
+    fn test_import_from_legacy_whitelist() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
 
-    /// Internal method to verify the predecessor was the Tenderbox Foundation account ID.
-    fn assert_called_by_foundation(&self) {
-        assert_eq!(
-            &env::predecessor_account_id(),
-            &self.foundation_account_id,
-            "Can only be called by the Tenderbox Foundation"
-        );
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        assert_eq!(contract.add_import_candidates(vec![account_tender(), account_factory()]), 2);
+        contract.import_from(account_legacy_whitelist(), 0, 10);
+
+        // Only the accounts the legacy whitelist confirms are imported
+        context.predecessor_account_id = account_verified();
+        testing_env_with_promise_results(context.clone(), PromiseResult::Successful(b"true".to_vec()));
+        assert!(contract.on_legacy_entry_checked(account_tender(), account_legacy_whitelist()));
+        assert!(!contract.on_legacy_entry_checked(account_tender(), account_legacy_whitelist()));
+        testing_env_with_promise_results(context.clone(), PromiseResult::Successful(b"false".to_vec()));
+        assert!(!contract.on_legacy_entry_checked(account_factory(), account_legacy_whitelist()));
+
+        assert!(contract.is_verified(account_tender()));
+        assert!(!contract.is_verified(account_factory()));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use near_sdk::{testing_env, MockedBlockchain};
+    #[test]
+    #[should_panic(expected = "E428: Can import between 1 and 10 accounts at once")]
+    fn test_import_from_empty_batch() {
+        let context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
+        testing_env!(context.clone());
 
-    mod test_utils;
-    use test_utils::*;
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        contract.add_import_candidates(vec![account_tender()]);
+        contract.import_from(account_legacy_whitelist(), 1, 10);
+    }
 
     #[test]
-    fn test_verified() {
+    fn test_review_bounty() {
         let mut context = VMContextBuilder::new()
             .current_account_id(account_verified())
             .predecessor_account_id(account_tenderbox())
             .finish();
         testing_env!(context.clone());
 
-        let mut contract = VerifiedTenderContract::new(account_near());
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        contract.add_verifier(account_verifier());
 
-        // Check initial list of verified tenders
-        context.is_view = true;
+        context.predecessor_account_id = account_tender();
         testing_env!(context.clone());
-        assert!(!contract.is_verified(account_tender()));
+        assert!(contract.request_verification());
+        assert!(!contract.request_verification());
 
-        // Adding to verified list by foundation
-        context.is_view = false;
+        // Two accounts fund the review, one of them takes its share back
+        context.predecessor_account_id = account_tender_owner();
+        context.attached_deposit = ntoy(2);
         testing_env!(context.clone());
-        assert!(contract.add_tender(account_tender()));
+        assert_eq!(contract.fund_review(account_tender()).0, ntoy(2));
 
-        // Checking it's verified now
-        context.is_view = true;
+        context.predecessor_account_id = account_supplier();
+        context.attached_deposit = ntoy(1);
         testing_env!(context.clone());
-        assert!(contract.is_verified(account_tender()));
+        assert_eq!(contract.fund_review(account_tender()).0, ntoy(3));
 
-        // Adding again. Should return false
-        context.is_view = false;
+        context.attached_deposit = 0;
         testing_env!(context.clone());
-        assert!(!contract.add_tender(account_tender()));
+        contract.withdraw_review_bounty(account_tender());
+        assert_eq!(contract.get_review_bounty(account_tender()).0, ntoy(2));
 
-        // Checking the pool is still verified
-        context.is_view = true;
+        // Completing the review pays the bounty out to the verifier
+        context.predecessor_account_id = account_verifier();
         testing_env!(context.clone());
-        assert!(contract.is_verified(account_pool()));
+        assert!(contract.review_tender(account_tender(), true));
+        assert!(near_sdk::test_utils::get_logs()
+            .iter()
+            .any(|log| log.ends_with(&format!("is paid to @{}", account_verifier()))));
+        assert_eq!(contract.get_review_bounty(account_tender()).0, 0);
+        assert!(!contract.is_verification_requested(account_tender()));
+        assert!(contract.is_verified(account_tender()));
+    }
 
-        // Removing from the list of verified tenders(called verified).
-        context.is_view = false;
+    #[test]
+    fn test_review_bounty_left_by_factory() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
         testing_env!(context.clone());
-        assert!(contract.remove_tender(account_tender()));
 
-        // Checking the pool is not verified anymore
-        context.is_view = true;
-        testing_env!(context.clone());
-        assert!(!contract.is_verified(account_pool()));
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        contract.add_factory(account_factory());
 
-        // Removing again from the whitelist, should return false.
-        context.is_view = false;
+        context.predecessor_account_id = account_tender();
         testing_env!(context.clone());
-        assert!(!contract.remove_tender(account_tender()));
+        contract.request_verification();
 
-        // Checking the pool is still not verified
-        context.is_view = true;
+        context.predecessor_account_id = account_tender_owner();
+        context.attached_deposit = ntoy(2);
         testing_env!(context.clone());
-        assert!(!contract.is_verified(account_pool()));
+        contract.fund_review(account_tender());
 
-        // Adding again after it was removed. Should return true
-        context.is_view = false;
+        // A factory verifying the tender doesn't review the request, so the funders keep the bounty
+        context.predecessor_account_id = account_factory();
+        context.attached_deposit = 0;
         testing_env!(context.clone());
-        assert!(contract.add_tender(account_tender()));
+        contract.reserve_tender(account_tender(), "Furniture".to_string());
+        assert!(contract.add_tender(account_tender(), None));
+        assert!(!contract.is_verification_requested(account_tender()));
+        assert_eq!(contract.get_review_bounty(account_tender()).0, ntoy(2));
 
-        // Checking the pool is now verified again
-        context.is_view = true;
+        context.predecessor_account_id = account_tender_owner();
         testing_env!(context.clone());
-        assert!(contract.is_verified(account_pool()));
+        contract.withdraw_review_bounty(account_tender());
+        assert_eq!(contract.get_review_bounty(account_tender()).0, 0);
     }
 
     #[test]
-    #[should_panic(expected = "Can only be called by Tenderbox Foundation")]
-    fn test_factory_verified_fail() {
+    #[should_panic(expected = "E547: The tender has no pending verification request")]
+    fn test_fund_review_without_request() {
         let mut context = VMContextBuilder::new()
             .current_account_id(account_verified())
             .predecessor_account_id(account_tenderbox())
@@ -227,16 +1753,15 @@ mod tests {
 
         let mut contract = VerifyTenderContract::new(account_tenderbox());
 
-        // Trying ot add to the verified list by NOT verified factory.
-        context.is_view = false;
-        context.predecessor_account_id = account_factory();
+        context.predecessor_account_id = account_tender_owner();
+        context.attached_deposit = ntoy(1);
         testing_env!(context.clone());
-        assert!(contract.add_tender(account_tender()));
+        contract.fund_review(account_tender());
     }
 
     #[test]
-    #[should_panic(expected = "Can only be called by Tenderbox Foundation")]
-    fn test_trying_to_verify_factory() {
+    #[should_panic(expected = "E106: Can only be called by the Tenderbox Foundation or an appointed verifier")]
+    fn test_review_tender_by_unknown_account() {
         let mut context = VMContextBuilder::new()
             .current_account_id(account_verified())
             .predecessor_account_id(account_tenderbox())
@@ -245,16 +1770,15 @@ mod tests {
 
         let mut contract = VerifyTenderContract::new(account_tenderbox());
 
-        // Trying to verify the factory not initiated by the Tenderbox Foundation.
-        context.is_view = false;
-        context.predecessor_account_id = account_tenderfactory();
+        context.predecessor_account_id = account_tender();
         testing_env!(context.clone());
-        assert!(contract.add_factory(account_tenderfactory()));
+        contract.request_verification();
+        contract.review_tender(account_tender(), true);
     }
 
     #[test]
-    #[should_panic(expected = "Can only be called by Tenderbox Foundation")]
-    fn test_trying_to_remove_by_factory() {
+    #[should_panic(expected = "E538: The tender is already verified")]
+    fn test_fund_review_of_verified_tender() {
         let mut context = VMContextBuilder::new()
             .current_account_id(account_verified())
             .predecessor_account_id(account_tenderbox())
@@ -262,75 +1786,110 @@ mod tests {
         testing_env!(context.clone());
 
         let mut contract = VerifyTenderContract::new(account_tenderbox());
+        contract.add_tender(account_tender(), None);
 
-        // Adding factory
-        context.is_view = false;
+        context.attached_deposit = ntoy(1);
         testing_env!(context.clone());
-        assert!(contract.add_factory(account_factory()));
+        contract.fund_review(account_tender());
+    }
+
+    #[test]
+    fn test_factory_record() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .block_timestamp(1_000)
+            .finish();
+        testing_env!(context.clone());
+
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        contract.add_factory(account_factory());
+        contract.set_factory_scope(account_factory(), vec!["Furniture".to_string()], Some(1.into()));
 
-        // Trying to remove the tender by the factory.
         context.predecessor_account_id = account_factory();
         testing_env!(context.clone());
-        assert!(contract.remove_tender(account_tender()));
+        contract.reserve_tender(account_tender(), "Furniture".to_string());
+        assert!(contract.add_tender(account_tender(), None));
+
+        let record = contract.get_factory_record(account_factory()).unwrap();
+        assert_eq!(record.verified_at.0, 1_000);
+        assert_eq!(record.verified_by, account_tenderbox());
+        assert_eq!(record.industries, vec!["Furniture".to_string()]);
+        assert_eq!(record.tenders_verified.0, 1);
     }
 
     #[test]
-    fn test_verified_factory() {
+    #[should_panic(expected = "E540: The factory reached its quota of verified tenders")]
+    fn test_factory_quota_reached() {
         let mut context = VMContextBuilder::new()
             .current_account_id(account_verified())
             .predecessor_account_id(account_tenderbox())
             .finish();
         testing_env!(context.clone());
 
-        let mut contract = TenderboxContract::new(account_tenderbox());
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        contract.add_factory(account_factory());
+        contract.set_factory_scope(account_factory(), vec![], Some(0.into()));
 
-        // Check the factory is not verified
-        context.is_view = true;
+        context.predecessor_account_id = account_factory();
         testing_env!(context.clone());
-        assert!(!contract.is_factory_verified(account_factory()));
+        contract.reserve_tender(account_tender(), "Furniture".to_string());
+    }
 
-        // Verified factory
-        context.is_view = false;
+    #[test]
+    #[should_panic(expected = "E548: The industry is outside the scope of the factory")]
+    fn test_reserve_tender_out_of_scope() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
         testing_env!(context.clone());
-        assert!(contract.add_factory(account_factory()));
 
-        // Check the factory is verified now(whitelisted)
-        context.is_view = true;
-        testing_env!(context.clone());
-        assert!(contract.is_factory_verified(account_factory()));
-        // Check the tender is not verified
-        assert!(!contract.is_verified(account_tender()));
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        contract.add_factory(account_factory());
+        contract.set_factory_scope(account_factory(), vec!["Furniture".to_string()], None);
 
-        // Adding to list of verified tenders by foundation
-        context.is_view = false;
         context.predecessor_account_id = account_factory();
         testing_env!(context.clone());
-        assert!(contract.add_tender(account_tender()));
+        contract.reserve_tender(account_tender(), "Construction".to_string());
+    }
 
-        // Checking it's verified now
-        context.is_view = true;
+    #[test]
+    fn test_release_tender() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
         testing_env!(context.clone());
-        assert!(contract.is_verified(account_pool()));
 
-        // Removing the tender from the list of verified tenders by the Tenderbox foundation.
-        context.is_view = false;
-        context.predecessor_account_id = account_tenderbox();
-        testing_env!(context.clone());
-        assert!(contract.remove_tender(account_tender()));
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        contract.add_factory(account_factory());
+        contract.set_factory_scope(account_factory(), vec![], Some(1.into()));
 
-        // Checking the tender is not verified anymore
-        context.is_view = true;
+        // The failed deployment gives the quota back to the factory
+        context.predecessor_account_id = account_factory();
         testing_env!(context.clone());
-        assert!(!contract.is_verified(account_tender()));
+        assert!(contract.reserve_tender(account_tender(), "Furniture".to_string()));
+        assert!(contract.release_tender(account_tender()));
+        assert!(!contract.release_tender(account_tender()));
+        assert_eq!(contract.get_factory_record(account_factory()).unwrap().tenders_verified.0, 0);
+        assert!(contract.reserve_tender(account_tender(), "Furniture".to_string()));
+    }
 
-        // Removing the factory
-        context.is_view = false;
+    #[test]
+    #[should_panic(expected = "E549: The factory has to reserve the tender before verifying it")]
+    fn test_add_tender_without_reservation() {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(account_verified())
+            .predecessor_account_id(account_tenderbox())
+            .finish();
         testing_env!(context.clone());
-        assert!(contract.remove_factory(account_factory()));
 
-        // Check the factory is not verified anymore
-        context.is_view = true;
+        let mut contract = VerifyTenderContract::new(account_tenderbox());
+        contract.add_factory(account_factory());
+
+        context.predecessor_account_id = account_factory();
         testing_env!(context.clone());
-        assert!(!contract.is_factory_verified(account_factory()));
+        contract.add_tender(account_tender(), None);
     }
-}
\ No newline at end of file
+}